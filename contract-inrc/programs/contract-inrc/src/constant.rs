@@ -1,15 +1,58 @@
 use anchor_lang::prelude::*;
-use hex_literal::hex;
 
 #[constant]
-pub const LIQUIDATION_BONUS: u64 = 5;
-pub const LIQUIDATION_THRESHOLD: u64 = 150; 
 pub const MINT_DECIMAL: u8 = 6; 
-pub const MIN_HEALTH_FACTOR: u64 = 120; 
+pub const MAX_LIQUIDATION_BONUS: u64 = 20;
+pub const MAX_MIN_HEALTH_FACTOR: u64 = 1000;
 pub const MAX_AGE: u64 = 60; 
+pub const MAX_CONFIG_UPDATE_DELAY: i64 = 30 * 24 * 60 * 60;
+pub const MAX_PORTFOLIO_ASSETS: u8 = 8;
+pub const MAX_COLLATERAL_TYPES: u8 = 16;
+pub const MAX_SORTED_POSITIONS: usize = 2048;
+pub const NICR_DECIMALS: u8 = 9;
+pub const MAX_PRICE_OBSERVATIONS: usize = 32;
+pub const PRICE_OBSERVATION_INTERVAL: i64 = 60;
+pub const MAX_TWAP_WINDOW: i64 = 30 * 60;
+pub const RATE_PRECISION: u128 = 1_000_000_000_000_000_000;
+pub const POOL_PRECISION: u128 = 1_000_000_000_000_000_000;
+pub const POOL_SCALE_FACTOR: u128 = 1_000_000_000;
+// per-second rate that compounds to roughly 100% a year
+pub const MAX_STABILITY_FEE_RATE: u128 = 21_979_553_151;
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const MAX_FEE_BPS: u16 = 1_000;
+// below this health factor a position is underwater and can be closed out in full
+pub const MAX_FULL_LIQUIDATION_HEALTH_FACTOR: u64 = 100;
 pub const TARGET_PRICE_DECIMALS: i32 = 8;
+pub const MIN_KEEPER_PRICE_EXPO: i32 = -18;
+pub const MAX_KEEPER_PRICE_EXPO: i32 = 0;
+// a portfolio instruction takes a (CollateralType, price feed, keeper feed,
+// price history) group of remaining accounts for every portfolio balance
+pub const PORTFOLIO_ACCOUNTS_PER_BALANCE: usize = 4;
+pub const PAUSE_MINT: u8 = 1 << 0;
+pub const PAUSE_WITHDRAW: u8 = 1 << 1;
+pub const PAUSE_LIQUIDATE: u8 = 1 << 2;
+pub const PAUSE_DEPOSIT: u8 = 1 << 3;
+pub const PAUSE_REPAY: u8 = 1 << 4;
+pub const PAUSE_ALL: u8 = PAUSE_MINT | PAUSE_WITHDRAW | PAUSE_LIQUIDATE | PAUSE_DEPOSIT | PAUSE_REPAY;
 pub const SEED_CONFIG_ACCOUNT: &[u8] = b"config";
 pub const SEED_MINT_ACCOUNT: &[u8] = b"inrc_mint";
 pub const SEED_TREASURY_AUTHORITY: &[u8] = b"treasury_authority";
 pub const SEED_COLLATERAL_ACCOUNT: &[u8] = b"user_collateral";
-pub const USDC_INR_FEED_ID_BYTES: [u8; 32] = hex!["2d3a776c7c2e4f014168c07e0b57e7a7f45b7e8d641d4c2b92d6e3f5b7e8d641"];
\ No newline at end of file
+pub const SEED_PENDING_CONFIG_UPDATE: &[u8] = b"pending_config_update";
+pub const SEED_COLLATERAL_TYPE: &[u8] = b"collateral_type";
+pub const SEED_PENDING_COLLATERAL_TYPE_UPDATE: &[u8] = b"pending_collateral_type_update";
+pub const SEED_PORTFOLIO: &[u8] = b"portfolio";
+pub const SEED_FEE_VAULT: &[u8] = b"fee_vault";
+pub const SEED_PROTOCOL_STATE: &[u8] = b"protocol_state";
+pub const SEED_AUCTION: &[u8] = b"auction";
+pub const SEED_INSURANCE_VAULT: &[u8] = b"insurance_vault";
+pub const SEED_STABILITY_POOL: &[u8] = b"stability_pool";
+pub const SEED_STABILITY_POOL_VAULT: &[u8] = b"stability_pool_vault";
+pub const SEED_STABILITY_POOL_EPOCH: &[u8] = b"stability_pool_epoch";
+pub const SEED_STABILITY_DEPOSIT: &[u8] = b"stability_deposit";
+pub const SEED_PSM: &[u8] = b"psm";
+pub const SEED_PSM_RESERVE: &[u8] = b"psm_reserve";
+pub const SEED_KEEPER_FEED: &[u8] = b"keeper_feed";
+pub const SEED_PRICE_HISTORY: &[u8] = b"price_history";
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+pub const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
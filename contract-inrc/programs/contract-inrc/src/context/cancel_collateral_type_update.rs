@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    Config, ProtocolState, ErrorCode, PendingCollateralTypeUpdate, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE, SEED_PENDING_COLLATERAL_TYPE_UPDATE,
+};
+
+#[derive(Accounts)]
+pub struct CancelCollateralTypeUpdate<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [SEED_PENDING_COLLATERAL_TYPE_UPDATE, pending_collateral_type_update.collateral_mint.as_ref()],
+        bump = pending_collateral_type_update.bump,
+    )]
+    pub pending_collateral_type_update: Account<'info, PendingCollateralTypeUpdate>,
+}
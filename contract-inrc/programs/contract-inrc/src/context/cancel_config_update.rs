@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, ProtocolState, ErrorCode, PendingConfigUpdate, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE, SEED_PENDING_CONFIG_UPDATE};
+
+#[derive(Accounts)]
+pub struct CancelConfigUpdate<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [SEED_PENDING_CONFIG_UPDATE],
+        bump = pending_config_update.bump,
+    )]
+    pub pending_config_update: Account<'info, PendingConfigUpdate>,
+}
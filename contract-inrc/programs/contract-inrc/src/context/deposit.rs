@@ -6,32 +6,46 @@ use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
 use crate::{
-    Config, UserCollateral, SEED_CONFIG_ACCOUNT, SEED_MINT_ACCOUNT, SEED_TREASURY_AUTHORITY, SEED_COLLATERAL_ACCOUNT, USDC_INR_FEED_ID_BYTES,
+    CollateralType, SortedPositions, Config, ProtocolState, ErrorCode, UserCollateral, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE, SEED_TREASURY_AUTHORITY, SEED_COLLATERAL_ACCOUNT, SEED_COLLATERAL_TYPE,
 };
 
 #[derive(Accounts)]
-pub struct DepositUsdcAndMintInrc<'info> {
+#[instruction(position_id: u64)]
+pub struct DepositCollateral<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
     #[account(
+        mut,
         seeds = [SEED_CONFIG_ACCOUNT],
         bump,
     )]
     pub config: Account<'info, Config>,
-    
+
     #[account(
         mut,
-        seeds = [SEED_MINT_ACCOUNT],
-        bump,
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        seeds = [SEED_COLLATERAL_TYPE, collateral_mint.key().as_ref()],
+        bump = collateral_type.bump,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+
+    #[account(
+        mut,
+        address = collateral_type.sorted_positions,
     )]
-    pub inrc_mint: Account<'info, Mint>,
+    pub sorted_positions: AccountLoader<'info, SortedPositions>,
 
     #[account(
         mut,
-        associated_token::mint = usdc_mint,
+        associated_token::mint = collateral_mint,
         associated_token::authority = signer,
     )]
-    pub user_usdc_account: Account<'info, TokenAccount>,
+    pub user_collateral_account: Account<'info, TokenAccount>,
 
     /// CHECK: This is a PDA for the mint authority
     #[account(
@@ -41,40 +55,23 @@ pub struct DepositUsdcAndMintInrc<'info> {
     pub treasury_authority: AccountInfo<'info>,
 
     #[account(
-        init_if_needed, 
-        payer = signer,
-        seeds = [SEED_COLLATERAL_ACCOUNT, signer.key().as_ref()], 
-        bump,
-        space = 8 + UserCollateral::INIT_SPACE, 
+        mut,
+        seeds = [SEED_COLLATERAL_ACCOUNT, signer.key().as_ref(), &position_id.to_le_bytes()], 
+        bump = user_collateral.bump,
+        has_one = collateral_mint @ ErrorCode::CollateralTypeMismatch,
     )]
     pub user_collateral: Account<'info, UserCollateral>,
 
     #[account(
-        init_if_needed,
-        payer = signer,
-        associated_token::mint = usdc_mint,
+        mut,
+        associated_token::mint = collateral_mint,
         associated_token::authority = treasury_authority,
     )]
-    pub usdc_treasury_account: Account<'info, TokenAccount>,
-
-    #[account(
-        init_if_needed,
-        payer = signer,
-        associated_token::mint = inrc_mint,
-        associated_token::authority = signer,
-    )]
-    pub user_inrc_account: Account<'info, TokenAccount>,
-
-    /// CHECK: This is a price feed
-     #[account(
-        address = Pubkey::new_from_array(USDC_INR_FEED_ID_BYTES),
-    )]
-    pub usdc_inr_price_feed: AccountInfo<'info>,
+    pub collateral_vault: Account<'info, TokenAccount>,
 
-    pub usdc_mint: Account<'info, Mint>,
+    pub collateral_mint: Account<'info, Mint>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
-    pub clock: Sysvar<'info, Clock>,
 }
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    CollateralType, Config, ProtocolState, Portfolio, SEED_COLLATERAL_TYPE, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE, SEED_PORTFOLIO, SEED_TREASURY_AUTHORITY,
+};
+
+#[derive(Accounts)]
+pub struct DepositPortfolioCollateral<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        seeds = [SEED_COLLATERAL_TYPE, collateral_mint.key().as_ref()],
+        bump = collateral_type.bump,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        seeds = [SEED_PORTFOLIO, signer.key().as_ref()],
+        bump,
+        space = 8 + Portfolio::INIT_SPACE,
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    #[account(
+        mut,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = signer,
+    )]
+    pub user_collateral_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA for the mint authority
+    #[account(
+        seeds = [SEED_TREASURY_AUTHORITY],
+        bump = config.treasury_authority_bump,
+    )]
+    pub treasury_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = treasury_authority,
+    )]
+    pub collateral_vault: Account<'info, TokenAccount>,
+
+    pub collateral_mint: Account<'info, Mint>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
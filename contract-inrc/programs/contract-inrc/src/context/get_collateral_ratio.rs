@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, ProtocolState, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE};
+
+// collateral is valued at the prices recorded by update_collateral_price,
+// so no price feeds are passed
+#[derive(Accounts)]
+pub struct GetCollateralRatio<'info> {
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+}
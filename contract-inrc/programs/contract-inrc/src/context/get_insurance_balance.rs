@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::{Config, ProtocolState, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE, SEED_INSURANCE_VAULT};
+
+#[derive(Accounts)]
+pub struct GetInsuranceBalance<'info> {
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        seeds = [SEED_INSURANCE_VAULT],
+        bump = config.insurance_vault_bump,
+    )]
+    pub insurance_vault: Account<'info, TokenAccount>,
+}
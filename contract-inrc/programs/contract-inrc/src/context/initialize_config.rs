@@ -1,10 +1,41 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token};
+use anchor_spl::token::{Mint, Token, TokenAccount};
 
 use crate::{
-    Config, SEED_CONFIG_ACCOUNT, SEED_MINT_ACCOUNT, SEED_TREASURY_AUTHORITY,MINT_DECIMAL,
+    Config, LiquidationMode, PriceMode, ProtocolState, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE, SEED_FEE_VAULT, SEED_INSURANCE_VAULT, SEED_MINT_ACCOUNT, SEED_TREASURY_AUTHORITY,MINT_DECIMAL,
 };
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InitConfigParams {
+    pub usdc_price_feed: Pubkey,
+    pub usdc_price_feed_id: [u8; 32],
+    pub usdc_keeper_feed: Pubkey,
+    pub config_update_delay: i64,
+    pub stability_fee_rate: u128,
+    pub mint_fee_bps: u16,
+    pub redeem_fee_bps: u16,
+    pub debt_ceiling: u64,
+    pub min_position_debt: u64,
+    pub close_factor_bps: u16,
+    pub full_liquidation_health_factor: u64,
+    pub liquidation_dust: u64,
+    pub liquidation_mode: LiquidationMode,
+    pub auction_start_discount_bps: u16,
+    pub auction_discount_bps_per_second: u16,
+    pub auction_max_discount_bps: u16,
+    pub auction_duration: i64,
+    pub insurance_share_bps: u16,
+    pub psm_fee_in_bps: u16,
+    pub psm_fee_out_bps: u16,
+    pub psm_capacity: u64,
+    pub redemption_fee_bps: u16,
+    pub max_price_confidence_bps: u16,
+    pub max_oracle_deviation_bps: u16,
+    pub liquidation_price_mode: PriceMode,
+    pub auction_price_mode: PriceMode,
+    pub twap_window: i64,
+}
+
 #[derive(Accounts)]
 pub struct InitializeConfig<'info> {
     #[account(mut)]
@@ -19,6 +50,15 @@ pub struct InitializeConfig<'info> {
     )]
     pub config: Account<'info, Config>,
 
+    #[account(
+        init,
+        payer = signer,
+        seeds = [SEED_PROTOCOL_STATE],
+        bump,
+        space = 8 + ProtocolState::INIT_SPACE,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
     #[account(
         init,
         payer = signer,
@@ -40,6 +80,30 @@ pub struct InitializeConfig<'info> {
     )]
     pub treasury_authority: AccountInfo<'info>,
 
+    // collects mint and redeem fees in inrc
+    #[account(
+        init,
+        payer = signer,
+        seeds = [SEED_FEE_VAULT],
+        bump,
+        token::mint = inrc_mint,
+        token::authority = treasury_authority,
+        token::token_program = token_program,
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    // usdc backstop for bad debt, kept apart from user collateral
+    #[account(
+        init,
+        payer = signer,
+        seeds = [SEED_INSURANCE_VAULT],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = treasury_authority,
+        token::token_program = token_program,
+    )]
+    pub insurance_vault: Account<'info, TokenAccount>,
+
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
 }
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{Config, ProtocolState, ErrorCode, KeeperFeed, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE, SEED_KEEPER_FEED};
+
+#[derive(Accounts)]
+pub struct InitializeKeeperFeed<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [SEED_KEEPER_FEED, mint.key().as_ref()],
+        bump,
+        space = 8 + KeeperFeed::INIT_SPACE,
+    )]
+    pub keeper_feed: Account<'info, KeeperFeed>,
+
+    pub mint: Account<'info, Mint>,
+    pub system_program: Program<'info, System>,
+}
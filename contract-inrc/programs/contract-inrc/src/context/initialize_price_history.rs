@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{CollateralType, Config, ProtocolState, ErrorCode, PriceHistory, SEED_COLLATERAL_TYPE, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE, SEED_PRICE_HISTORY};
+
+#[derive(Accounts)]
+pub struct InitializePriceHistory<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        seeds = [SEED_COLLATERAL_TYPE, collateral_type.mint.as_ref()],
+        bump = collateral_type.bump,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [SEED_PRICE_HISTORY, collateral_type.mint.as_ref()],
+        bump,
+        space = 8 + PriceHistory::INIT_SPACE,
+    )]
+    pub price_history: Box<Account<'info, PriceHistory>>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    Config, ProtocolState, ErrorCode, Psm, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE, SEED_PSM, SEED_PSM_RESERVE, SEED_TREASURY_AUTHORITY,
+};
+
+#[derive(Accounts)]
+pub struct InitializePsm<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [SEED_PSM],
+        bump,
+        space = 8 + Psm::INIT_SPACE,
+    )]
+    pub psm: Account<'info, Psm>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [SEED_PSM_RESERVE],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = treasury_authority,
+        token::token_program = token_program,
+    )]
+    pub psm_reserve: Account<'info, TokenAccount>,
+
+    #[account(
+        address = config.usdc_mint,
+    )]
+    pub usdc_mint: Account<'info, Mint>,
+
+    /// CHECK: This is a PDA for the mint authority
+    #[account(
+        seeds = [SEED_TREASURY_AUTHORITY],
+        bump = config.treasury_authority_bump,
+    )]
+    pub treasury_authority: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
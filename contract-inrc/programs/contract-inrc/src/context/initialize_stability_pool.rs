@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    Config, ProtocolState, ErrorCode, StabilityPool, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE, SEED_MINT_ACCOUNT, SEED_STABILITY_POOL, SEED_STABILITY_POOL_VAULT, SEED_TREASURY_AUTHORITY,
+};
+
+#[derive(Accounts)]
+pub struct InitializeStabilityPool<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [SEED_STABILITY_POOL],
+        bump,
+        space = 8 + StabilityPool::INIT_SPACE,
+    )]
+    pub stability_pool: Account<'info, StabilityPool>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [SEED_STABILITY_POOL_VAULT],
+        bump,
+        token::mint = inrc_mint,
+        token::authority = treasury_authority,
+        token::token_program = token_program,
+    )]
+    pub stability_pool_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [SEED_MINT_ACCOUNT],
+        bump,
+    )]
+    pub inrc_mint: Account<'info, Mint>,
+
+    /// CHECK: This is a PDA for the mint authority
+    #[account(
+        seeds = [SEED_TREASURY_AUTHORITY],
+        bump = config.treasury_authority_bump,
+    )]
+    pub treasury_authority: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
@@ -2,20 +2,42 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use anchor_spl::associated_token::AssociatedToken;
 use crate::{
-    Config, UserCollateral, SEED_CONFIG_ACCOUNT, SEED_MINT_ACCOUNT, SEED_TREASURY_AUTHORITY, SEED_COLLATERAL_ACCOUNT, USDC_INR_FEED_ID_BYTES,
+    CollateralType, SortedPositions, Config, KeeperFeed, PriceHistory, ProtocolState, ErrorCode, UserCollateral, SEED_CONFIG_ACCOUNT, SEED_INSURANCE_VAULT, SEED_PROTOCOL_STATE, SEED_MINT_ACCOUNT, SEED_TREASURY_AUTHORITY, SEED_COLLATERAL_ACCOUNT, SEED_COLLATERAL_TYPE, SEED_KEEPER_FEED, SEED_PRICE_HISTORY,
 };
 
 #[derive(Accounts)]
+#[instruction(position_id: u64)]
 pub struct Liquidate<'info> {
     #[account(mut)]
     pub liquidator: Signer<'info>,
 
     #[account(
+        mut,
         seeds = [SEED_CONFIG_ACCOUNT],
         bump,
     )]
     pub config: Account<'info, Config>,
 
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_TYPE, collateral_mint.key().as_ref()],
+        bump = collateral_type.bump,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+
+    #[account(
+        mut,
+        address = collateral_type.sorted_positions,
+    )]
+    pub sorted_positions: AccountLoader<'info, SortedPositions>,
+
     #[account(
         mut,
         seeds = [SEED_MINT_ACCOUNT],
@@ -32,10 +54,10 @@ pub struct Liquidate<'info> {
 
     #[account(
         mut,
-        associated_token::mint = config.usdc_mint,
+        associated_token::mint = collateral_mint,
         associated_token::authority = liquidator,
     )]
-    pub liquidator_usdc_account: Account<'info, TokenAccount>,
+    pub liquidator_collateral_account: Account<'info, TokenAccount>,
 
     /// CHECK: This is a PDA for the mint authority
     #[account(
@@ -46,10 +68,17 @@ pub struct Liquidate<'info> {
 
     #[account(
         mut,
-        associated_token::mint = config.usdc_mint,
+        associated_token::mint = collateral_mint,
         associated_token::authority = treasury_authority,
     )]
-    pub treasury_usdc_account: Account<'info, TokenAccount>,
+    pub collateral_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [SEED_INSURANCE_VAULT],
+        bump = config.insurance_vault_bump,
+    )]
+    pub insurance_vault: Account<'info, TokenAccount>,
 
     /// CHECK: This is the original depositor to
     /// be liquidated
@@ -57,19 +86,33 @@ pub struct Liquidate<'info> {
 
     #[account(
         mut,
-        seeds = [SEED_COLLATERAL_ACCOUNT, user_to_liquidate.key().as_ref()], 
+        seeds = [SEED_COLLATERAL_ACCOUNT, user_to_liquidate.key().as_ref(), &position_id.to_le_bytes()], 
         bump = user_collateral.bump,
+        has_one = collateral_mint @ ErrorCode::CollateralTypeMismatch,
     )]
     pub user_collateral: Account<'info, UserCollateral>,
 
     /// CHECK: This is a price feed
     #[account(
-        address = Pubkey::new_from_array(USDC_INR_FEED_ID_BYTES),
+        address = collateral_type.price_feed,
+    )]
+    pub price_feed: AccountInfo<'info>,
+
+    #[account(
+        seeds = [SEED_KEEPER_FEED, collateral_type.mint.as_ref()],
+        bump = keeper_feed.bump,
+    )]
+    pub keeper_feed: Option<Account<'info, KeeperFeed>>,
+
+    #[account(
+        seeds = [SEED_PRICE_HISTORY, collateral_type.mint.as_ref()],
+        bump = price_history.bump,
     )]
-    pub usdc_inr_price_feed: AccountInfo<'info>,
+    pub price_history: Option<Box<Account<'info, PriceHistory>>>,
 
+    pub collateral_mint: Account<'info, Mint>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub clock: Sysvar<'info, Clock>,
-}
\ No newline at end of file
+}
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    CollateralType, Config, KeeperFeed, ProtocolState, Portfolio, SEED_COLLATERAL_TYPE, SEED_CONFIG_ACCOUNT, SEED_INSURANCE_VAULT, SEED_PROTOCOL_STATE, SEED_MINT_ACCOUNT, SEED_PORTFOLIO,
+    SEED_TREASURY_AUTHORITY, SEED_KEEPER_FEED,
+};
+
+// remaining accounts: a (CollateralType, price feed, keeper feed, price
+// history) quadruple for every portfolio balance, in portfolio order. the
+// program id stands in for a missing keeper feed or price history. the
+// CollateralTypes are writable, the portfolio debt is charged to them
+#[derive(Accounts)]
+pub struct LiquidatePortfolio<'info> {
+    #[account(mut)]
+    pub liquidator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    // the collateral the liquidator chose to seize
+    #[account(
+        seeds = [SEED_COLLATERAL_TYPE, collateral_mint.key().as_ref()],
+        bump = collateral_type.bump,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+
+    #[account(
+        mut,
+        seeds = [SEED_MINT_ACCOUNT],
+        bump,
+    )]
+    pub inrc_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = inrc_mint,
+        associated_token::authority = liquidator,
+    )]
+    pub liquidator_inrc_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = liquidator,
+    )]
+    pub liquidator_collateral_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA for the mint authority
+    #[account(
+        seeds = [SEED_TREASURY_AUTHORITY],
+        bump = config.treasury_authority_bump,
+    )]
+    pub treasury_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = treasury_authority,
+    )]
+    pub collateral_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [SEED_INSURANCE_VAULT],
+        bump = config.insurance_vault_bump,
+    )]
+    pub insurance_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: This is the owner of the portfolio
+    /// to be liquidated
+    pub portfolio_owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PORTFOLIO, portfolio_owner.key().as_ref()],
+        bump = portfolio.bump,
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// CHECK: This is a price feed
+    #[account(
+        address = collateral_type.price_feed,
+    )]
+    pub price_feed: AccountInfo<'info>,
+
+    #[account(
+        seeds = [SEED_KEEPER_FEED, collateral_type.mint.as_ref()],
+        bump = keeper_feed.bump,
+    )]
+    pub keeper_feed: Option<Account<'info, KeeperFeed>>,
+
+    pub collateral_mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    CollateralType, SortedPositions, Config, KeeperFeed, PriceHistory, ErrorCode, ProtocolState, StabilityPool, StabilityPoolEpoch, UserCollateral, SEED_COLLATERAL_ACCOUNT, SEED_COLLATERAL_TYPE,
+    SEED_CONFIG_ACCOUNT, SEED_MINT_ACCOUNT, SEED_PROTOCOL_STATE, SEED_STABILITY_POOL, SEED_STABILITY_POOL_EPOCH, SEED_STABILITY_POOL_VAULT,
+    SEED_TREASURY_AUTHORITY, SEED_KEEPER_FEED, SEED_PRICE_HISTORY,
+};
+
+#[derive(Accounts)]
+#[instruction(position_id: u64)]
+pub struct LiquidateWithStabilityPool<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_TYPE, collateral_mint.key().as_ref()],
+        bump = collateral_type.bump,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+
+    #[account(
+        mut,
+        address = collateral_type.sorted_positions,
+    )]
+    pub sorted_positions: AccountLoader<'info, SortedPositions>,
+
+    #[account(
+        mut,
+        seeds = [SEED_STABILITY_POOL],
+        bump = stability_pool.bump,
+    )]
+    pub stability_pool: Account<'info, StabilityPool>,
+
+    // created here after an offset has moved the pool to a new scale
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        seeds = [SEED_STABILITY_POOL_EPOCH, &stability_pool.epoch.to_le_bytes(), &stability_pool.scale.to_le_bytes()],
+        bump,
+        space = 8 + StabilityPoolEpoch::INIT_SPACE,
+    )]
+    pub current_epoch: Account<'info, StabilityPoolEpoch>,
+
+    #[account(
+        mut,
+        seeds = [SEED_STABILITY_POOL_VAULT],
+        bump = stability_pool.vault_bump,
+    )]
+    pub stability_pool_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [SEED_MINT_ACCOUNT],
+        bump,
+    )]
+    pub inrc_mint: Account<'info, Mint>,
+
+    /// CHECK: This is a PDA for the mint authority
+    #[account(
+        seeds = [SEED_TREASURY_AUTHORITY],
+        bump = config.treasury_authority_bump,
+    )]
+    pub treasury_authority: AccountInfo<'info>,
+
+    /// CHECK: This is the original depositor to
+    /// be liquidated
+    pub user_to_liquidate: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_ACCOUNT, user_to_liquidate.key().as_ref(), &position_id.to_le_bytes()],
+        bump = user_collateral.bump,
+        has_one = collateral_mint @ ErrorCode::CollateralTypeMismatch,
+    )]
+    pub user_collateral: Account<'info, UserCollateral>,
+
+    /// CHECK: This is a price feed
+    #[account(
+        address = collateral_type.price_feed,
+    )]
+    pub price_feed: AccountInfo<'info>,
+
+    #[account(
+        seeds = [SEED_KEEPER_FEED, collateral_type.mint.as_ref()],
+        bump = keeper_feed.bump,
+    )]
+    pub keeper_feed: Option<Account<'info, KeeperFeed>>,
+
+    #[account(
+        seeds = [SEED_PRICE_HISTORY, collateral_type.mint.as_ref()],
+        bump = price_history.bump,
+    )]
+    pub price_history: Option<Box<Account<'info, PriceHistory>>>,
+
+    pub collateral_mint: Account<'info, Mint>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
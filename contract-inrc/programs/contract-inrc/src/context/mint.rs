@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    CollateralType, SortedPositions, Config, ProtocolState, KeeperFeed, UserCollateral, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE, SEED_FEE_VAULT, SEED_MINT_ACCOUNT, SEED_TREASURY_AUTHORITY, SEED_COLLATERAL_ACCOUNT, SEED_COLLATERAL_TYPE, SEED_KEEPER_FEED,
+};
+
+#[derive(Accounts)]
+#[instruction(position_id: u64)]
+pub struct MintInrc<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_TYPE, user_collateral.collateral_mint.as_ref()],
+        bump = collateral_type.bump,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+
+    #[account(
+        mut,
+        address = collateral_type.sorted_positions,
+    )]
+    pub sorted_positions: AccountLoader<'info, SortedPositions>,
+
+    #[account(
+        mut,
+        seeds = [SEED_MINT_ACCOUNT],
+        bump,
+    )]
+    pub inrc_mint: Account<'info, Mint>,
+
+    /// CHECK: This is a PDA for the mint authority
+    #[account(
+        seeds = [SEED_TREASURY_AUTHORITY],
+        bump = config.treasury_authority_bump,
+    )]
+    pub treasury_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_ACCOUNT, signer.key().as_ref(), &position_id.to_le_bytes()], 
+        bump = user_collateral.bump,
+    )]
+    pub user_collateral: Account<'info, UserCollateral>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = inrc_mint,
+        associated_token::authority = signer,
+    )]
+    pub user_inrc_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [SEED_FEE_VAULT],
+        bump = config.fee_vault_bump,
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: This is a price feed
+    #[account(
+        address = collateral_type.price_feed,
+    )]
+    pub price_feed: AccountInfo<'info>,
+
+    #[account(
+        seeds = [SEED_KEEPER_FEED, collateral_type.mint.as_ref()],
+        bump = keeper_feed.bump,
+    )]
+    pub keeper_feed: Option<Account<'info, KeeperFeed>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    Config, ProtocolState, Portfolio, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE, SEED_FEE_VAULT, SEED_MINT_ACCOUNT, SEED_PORTFOLIO, SEED_TREASURY_AUTHORITY,
+};
+
+// remaining accounts: a (CollateralType, price feed, keeper feed, price
+// history) quadruple for every portfolio balance, in portfolio order. the
+// program id stands in for a missing keeper feed or price history. the
+// CollateralTypes are writable, the portfolio debt is charged to them
+#[derive(Accounts)]
+pub struct MintPortfolioInrc<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PORTFOLIO, signer.key().as_ref()],
+        bump = portfolio.bump,
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    #[account(
+        mut,
+        seeds = [SEED_MINT_ACCOUNT],
+        bump,
+    )]
+    pub inrc_mint: Account<'info, Mint>,
+
+    /// CHECK: This is a PDA for the mint authority
+    #[account(
+        seeds = [SEED_TREASURY_AUTHORITY],
+        bump = config.treasury_authority_bump,
+    )]
+    pub treasury_authority: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = inrc_mint,
+        associated_token::authority = signer,
+    )]
+    pub user_inrc_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [SEED_FEE_VAULT],
+        bump = config.fee_vault_bump,
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -1,11 +1,149 @@
 pub mod initialize_config;
 pub use initialize_config::*;
 
+pub mod open_position;
+pub use open_position::*;
+
 pub mod deposit;
 pub use deposit::*;
 
+pub mod mint;
+pub use mint::*;
+
+pub mod repay;
+pub use repay::*;
+
+pub mod withdraw_collateral;
+pub use withdraw_collateral::*;
+
 pub mod withdraw;
 pub use withdraw::*;
 
+pub mod close_position;
+pub use close_position::*;
+
 pub mod liquidate;  
-pub use liquidate::*;
\ No newline at end of file
+pub use liquidate::*;
+
+pub mod propose_config_update;
+pub use propose_config_update::*;
+
+pub mod execute_config_update;
+pub use execute_config_update::*;
+
+pub mod cancel_config_update;
+pub use cancel_config_update::*;
+
+pub mod nominate_authority;
+pub use nominate_authority::*;
+
+pub mod accept_authority;
+pub use accept_authority::*;
+
+pub mod renounce_authority;
+pub use renounce_authority::*;
+
+pub mod set_guardian;
+pub use set_guardian::*;
+
+pub mod set_paused_flags;
+pub use set_paused_flags::*;
+
+pub mod register_collateral_type;
+pub use register_collateral_type::*;
+
+pub mod propose_collateral_type_update;
+pub use propose_collateral_type_update::*;
+
+pub mod execute_collateral_type_update;
+pub use execute_collateral_type_update::*;
+
+pub mod cancel_collateral_type_update;
+pub use cancel_collateral_type_update::*;
+
+pub mod deposit_portfolio_collateral;
+pub use deposit_portfolio_collateral::*;
+
+pub mod withdraw_portfolio_collateral;
+pub use withdraw_portfolio_collateral::*;
+
+pub mod mint_portfolio_inrc;
+pub use mint_portfolio_inrc::*;
+
+pub mod repay_portfolio_inrc;
+pub use repay_portfolio_inrc::*;
+
+pub mod liquidate_portfolio;
+pub use liquidate_portfolio::*;
+
+pub mod collect_fees;
+pub use collect_fees::*;
+
+pub mod update_collateral_price;
+pub use update_collateral_price::*;
+
+pub mod get_collateral_ratio;
+pub use get_collateral_ratio::*;
+
+pub mod start_auction;
+pub use start_auction::*;
+
+pub mod take;
+pub use take::*;
+
+pub mod reset_auction;
+pub use reset_auction::*;
+
+pub mod settle_bad_debt;
+pub use settle_bad_debt::*;
+
+pub mod settle_portfolio_bad_debt;
+pub use settle_portfolio_bad_debt::*;
+
+pub mod seed_insurance;
+pub use seed_insurance::*;
+
+pub mod get_insurance_balance;
+pub use get_insurance_balance::*;
+
+pub mod sweep_insurance_fees;
+pub use sweep_insurance_fees::*;
+
+pub mod initialize_stability_pool;
+pub use initialize_stability_pool::*;
+
+pub mod provide_to_stability_pool;
+pub use provide_to_stability_pool::*;
+
+pub mod withdraw_from_stability_pool;
+pub use withdraw_from_stability_pool::*;
+
+pub mod claim_stability_pool_gain;
+pub use claim_stability_pool_gain::*;
+
+pub mod liquidate_with_stability_pool;
+pub use liquidate_with_stability_pool::*;
+
+pub mod initialize_psm;
+pub use initialize_psm::*;
+
+pub mod psm_swap;
+pub use psm_swap::*;
+
+pub mod redeem;
+pub use redeem::*;
+
+pub mod initialize_keeper_feed;
+pub use initialize_keeper_feed::*;
+
+pub mod set_feed_keeper;
+pub use set_feed_keeper::*;
+
+pub mod update_keeper_feed;
+pub use update_keeper_feed::*;
+
+pub mod initialize_price_history;
+pub use initialize_price_history::*;
+
+pub mod record_price;
+pub use record_price::*;
\ No newline at end of file
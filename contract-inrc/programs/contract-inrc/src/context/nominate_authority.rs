@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, ProtocolState, ErrorCode, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE};
+
+#[derive(Accounts)]
+pub struct NominateAuthority<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{
+    CollateralType, UserCollateral, SEED_COLLATERAL_ACCOUNT, SEED_COLLATERAL_TYPE,
+};
+
+#[derive(Accounts)]
+#[instruction(position_id: u64)]
+pub struct OpenPosition<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_COLLATERAL_TYPE, collateral_mint.key().as_ref()],
+        bump = collateral_type.bump,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+
+    #[account(
+        init,
+        payer = signer,
+        seeds = [SEED_COLLATERAL_ACCOUNT, signer.key().as_ref(), &position_id.to_le_bytes()],
+        bump,
+        space = 8 + UserCollateral::INIT_SPACE,
+    )]
+    pub user_collateral: Account<'info, UserCollateral>,
+
+    pub collateral_mint: Account<'info, Mint>,
+    pub system_program: Program<'info, System>,
+}
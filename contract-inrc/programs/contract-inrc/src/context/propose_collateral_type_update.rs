@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    CollateralType, Config, ProtocolState, ErrorCode, PendingCollateralTypeUpdate, SEED_COLLATERAL_TYPE, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE,
+    SEED_PENDING_COLLATERAL_TYPE_UPDATE,
+};
+
+#[derive(Accounts)]
+pub struct ProposeCollateralTypeUpdate<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        seeds = [SEED_COLLATERAL_TYPE, collateral_type.mint.as_ref()],
+        bump = collateral_type.bump,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [SEED_PENDING_COLLATERAL_TYPE_UPDATE, collateral_type.mint.as_ref()],
+        bump,
+        space = 8 + PendingCollateralTypeUpdate::INIT_SPACE,
+    )]
+    pub pending_collateral_type_update: Account<'info, PendingCollateralTypeUpdate>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    Config, ProtocolState, StabilityDeposit, StabilityPool, StabilityPoolEpoch, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE, SEED_MINT_ACCOUNT, SEED_STABILITY_DEPOSIT, SEED_STABILITY_POOL,
+    SEED_STABILITY_POOL_EPOCH, SEED_STABILITY_POOL_VAULT,
+};
+
+#[derive(Accounts)]
+pub struct ProvideToStabilityPool<'info> {
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        mut,
+        seeds = [SEED_STABILITY_POOL],
+        bump = stability_pool.bump,
+    )]
+    pub stability_pool: Account<'info, StabilityPool>,
+
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        seeds = [SEED_STABILITY_DEPOSIT, depositor.key().as_ref()],
+        bump,
+        space = 8 + StabilityDeposit::INIT_SPACE,
+    )]
+    pub stability_deposit: Account<'info, StabilityDeposit>,
+
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        seeds = [SEED_STABILITY_POOL_EPOCH, &stability_pool.epoch.to_le_bytes(), &stability_pool.scale.to_le_bytes()],
+        bump,
+        space = 8 + StabilityPoolEpoch::INIT_SPACE,
+    )]
+    pub current_epoch: Account<'info, StabilityPoolEpoch>,
+
+    /// CHECK: the epoch and scale of the deposit's last snapshot, only
+    /// read while the deposit is non-zero
+    #[account(
+        seeds = [SEED_STABILITY_POOL_EPOCH, &stability_deposit.epoch.to_le_bytes(), &stability_deposit.scale.to_le_bytes()],
+        bump,
+    )]
+    pub depositor_epoch: AccountInfo<'info>,
+
+    /// CHECK: the scale after the deposit's last snapshot, which may not
+    /// exist yet
+    #[account(
+        seeds = [SEED_STABILITY_POOL_EPOCH, &stability_deposit.epoch.to_le_bytes(), &(stability_deposit.scale + 1).to_le_bytes()],
+        bump,
+    )]
+    pub depositor_next_scale: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_STABILITY_POOL_VAULT],
+        bump = stability_pool.vault_bump,
+    )]
+    pub stability_pool_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [SEED_MINT_ACCOUNT],
+        bump,
+    )]
+    pub inrc_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = inrc_mint,
+        associated_token::authority = depositor,
+    )]
+    pub depositor_inrc_account: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{CollateralType, Config, ProtocolState, KeeperFeed, PriceHistory, SEED_COLLATERAL_TYPE, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE, SEED_KEEPER_FEED, SEED_PRICE_HISTORY};
+
+#[derive(Accounts)]
+pub struct RecordPrice<'info> {
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        seeds = [SEED_COLLATERAL_TYPE, price_history.mint.as_ref()],
+        bump = collateral_type.bump,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PRICE_HISTORY, price_history.mint.as_ref()],
+        bump = price_history.bump,
+    )]
+    pub price_history: Box<Account<'info, PriceHistory>>,
+
+    /// CHECK: This is a price feed
+    #[account(
+        address = collateral_type.price_feed,
+    )]
+    pub price_feed: AccountInfo<'info>,
+
+    #[account(
+        seeds = [SEED_KEEPER_FEED, collateral_type.mint.as_ref()],
+        bump = keeper_feed.bump,
+    )]
+    pub keeper_feed: Option<Account<'info, KeeperFeed>>,
+}
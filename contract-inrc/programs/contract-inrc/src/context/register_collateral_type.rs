@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    CollateralType, Config, ProtocolState, SortedPositions, ErrorCode, SEED_COLLATERAL_TYPE, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE, SEED_TREASURY_AUTHORITY,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RegisterCollateralTypeParams {
+    pub price_feed: Pubkey,
+    pub price_feed_id: [u8; 32],
+    pub keeper_feed: Pubkey,
+    pub liquidation_threshold: u64,
+    pub liquidation_bonus: u64,
+    pub min_health_factor: u64,
+    pub debt_ceiling: u64,
+}
+
+#[derive(Accounts)]
+pub struct RegisterCollateralType<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [SEED_COLLATERAL_TYPE, collateral_mint.key().as_ref()],
+        bump,
+        space = 8 + CollateralType::INIT_SPACE,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+
+    // allocated by the caller in the same transaction, it is too large to
+    // be created here
+    #[account(zero)]
+    pub sorted_positions: AccountLoader<'info, SortedPositions>,
+
+    pub collateral_mint: Account<'info, Mint>,
+
+    /// CHECK: This is a PDA for the mint authority
+    #[account(
+        seeds = [SEED_TREASURY_AUTHORITY],
+        bump = config.treasury_authority_bump,
+    )]
+    pub treasury_authority: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = treasury_authority,
+    )]
+    pub collateral_vault: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::{Auction, Config, ProtocolState, SEED_AUCTION, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE};
+
+#[derive(Accounts)]
+pub struct ResetAuction<'info> {
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        mut,
+        seeds = [SEED_AUCTION, auction.owner.as_ref(), &auction.position_id.to_le_bytes()],
+        bump = auction.bump,
+    )]
+    pub auction: Account<'info, Auction>,
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::{Config, ProtocolState, ErrorCode, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE, SEED_INSURANCE_VAULT};
+
+#[derive(Accounts)]
+pub struct SeedInsurance<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        mut,
+        seeds = [SEED_INSURANCE_VAULT],
+        bump = config.insurance_vault_bump,
+    )]
+    pub insurance_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = config.usdc_mint,
+        token::authority = authority,
+    )]
+    pub authority_usdc_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
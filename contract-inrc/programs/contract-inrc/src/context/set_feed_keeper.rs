@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, ProtocolState, ErrorCode, KeeperFeed, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE, SEED_KEEPER_FEED};
+
+#[derive(Accounts)]
+pub struct SetFeedKeeper<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        mut,
+        seeds = [SEED_KEEPER_FEED, keeper_feed.mint.as_ref()],
+        bump = keeper_feed.bump,
+    )]
+    pub keeper_feed: Account<'info, KeeperFeed>,
+}
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::associated_token::AssociatedToken;
+use crate::{
+    CollateralType, SortedPositions, Config, KeeperFeed, ProtocolState, ErrorCode, UserCollateral, SEED_CONFIG_ACCOUNT, SEED_INSURANCE_VAULT, SEED_PROTOCOL_STATE, SEED_MINT_ACCOUNT, SEED_TREASURY_AUTHORITY, SEED_COLLATERAL_ACCOUNT, SEED_COLLATERAL_TYPE, SEED_KEEPER_FEED,
+};
+
+#[derive(Accounts)]
+#[instruction(position_id: u64)]
+pub struct SettleBadDebt<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_TYPE, collateral_mint.key().as_ref()],
+        bump = collateral_type.bump,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+
+    #[account(
+        mut,
+        address = collateral_type.sorted_positions,
+    )]
+    pub sorted_positions: AccountLoader<'info, SortedPositions>,
+
+    #[account(
+        mut,
+        seeds = [SEED_MINT_ACCOUNT],
+        bump,
+    )]
+    pub inrc_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = inrc_mint,
+        associated_token::authority = keeper,
+    )]
+    pub keeper_inrc_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = keeper,
+    )]
+    pub keeper_collateral_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA for the mint authority
+    #[account(
+        seeds = [SEED_TREASURY_AUTHORITY],
+        bump = config.treasury_authority_bump,
+    )]
+    pub treasury_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = treasury_authority,
+    )]
+    pub collateral_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [SEED_INSURANCE_VAULT],
+        bump = config.insurance_vault_bump,
+    )]
+    pub insurance_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: This is the usdc price feed
+    #[account(
+        address = config.usdc_price_feed,
+    )]
+    pub usdc_price_feed: AccountInfo<'info>,
+
+    #[account(
+        seeds = [SEED_KEEPER_FEED, config.usdc_mint.as_ref()],
+        bump = usdc_keeper_feed.bump,
+    )]
+    pub usdc_keeper_feed: Option<Account<'info, KeeperFeed>>,
+
+    #[account(
+        mut,
+        token::mint = config.usdc_mint,
+        token::authority = keeper,
+    )]
+    pub keeper_usdc_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is the owner of the
+    /// underwater position
+    pub position_owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_ACCOUNT, position_owner.key().as_ref(), &position_id.to_le_bytes()], 
+        bump = user_collateral.bump,
+        has_one = collateral_mint @ ErrorCode::CollateralTypeMismatch,
+    )]
+    pub user_collateral: Account<'info, UserCollateral>,
+
+    /// CHECK: This is a price feed
+    #[account(
+        address = collateral_type.price_feed,
+    )]
+    pub price_feed: AccountInfo<'info>,
+
+    #[account(
+        seeds = [SEED_KEEPER_FEED, collateral_type.mint.as_ref()],
+        bump = keeper_feed.bump,
+    )]
+    pub keeper_feed: Option<Account<'info, KeeperFeed>>,
+
+    pub collateral_mint: Account<'info, Mint>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{
+    Auction, CollateralType, SortedPositions, Config, ProtocolState, KeeperFeed, PriceHistory, ErrorCode, UserCollateral, SEED_AUCTION, SEED_COLLATERAL_ACCOUNT, SEED_COLLATERAL_TYPE, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE, SEED_KEEPER_FEED, SEED_PRICE_HISTORY,
+};
+
+#[derive(Accounts)]
+#[instruction(position_id: u64)]
+pub struct StartAuction<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        seeds = [SEED_COLLATERAL_TYPE, collateral_mint.key().as_ref()],
+        bump = collateral_type.bump,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+
+    #[account(
+        mut,
+        address = collateral_type.sorted_positions,
+    )]
+    pub sorted_positions: AccountLoader<'info, SortedPositions>,
+
+    /// CHECK: This is the owner of the position
+    /// put up for auction
+    pub position_owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_ACCOUNT, position_owner.key().as_ref(), &position_id.to_le_bytes()],
+        bump = user_collateral.bump,
+        has_one = collateral_mint @ ErrorCode::CollateralTypeMismatch,
+    )]
+    pub user_collateral: Account<'info, UserCollateral>,
+
+    #[account(
+        init,
+        payer = keeper,
+        seeds = [SEED_AUCTION, position_owner.key().as_ref(), &position_id.to_le_bytes()],
+        bump,
+        space = 8 + Auction::INIT_SPACE,
+    )]
+    pub auction: Account<'info, Auction>,
+
+    /// CHECK: This is a price feed
+    #[account(
+        address = collateral_type.price_feed,
+    )]
+    pub price_feed: AccountInfo<'info>,
+
+    #[account(
+        seeds = [SEED_KEEPER_FEED, collateral_type.mint.as_ref()],
+        bump = keeper_feed.bump,
+    )]
+    pub keeper_feed: Option<Account<'info, KeeperFeed>>,
+
+    #[account(
+        seeds = [SEED_PRICE_HISTORY, collateral_type.mint.as_ref()],
+        bump = price_history.bump,
+    )]
+    pub price_history: Option<Box<Account<'info, PriceHistory>>>,
+
+    pub collateral_mint: Account<'info, Mint>,
+    pub system_program: Program<'info, System>,
+}
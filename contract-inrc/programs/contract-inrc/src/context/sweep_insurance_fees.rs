@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    Config, ProtocolState, KeeperFeed, Psm, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE, SEED_FEE_VAULT, SEED_INSURANCE_VAULT, SEED_KEEPER_FEED, SEED_MINT_ACCOUNT, SEED_PSM, SEED_PSM_RESERVE, SEED_TREASURY_AUTHORITY,
+};
+
+#[derive(Accounts)]
+pub struct SweepInsuranceFees<'info> {
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PSM],
+        bump = psm.bump,
+    )]
+    pub psm: Account<'info, Psm>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PSM_RESERVE],
+        bump = psm.reserve_bump,
+    )]
+    pub psm_reserve: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [SEED_FEE_VAULT],
+        bump = config.fee_vault_bump,
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [SEED_INSURANCE_VAULT],
+        bump = config.insurance_vault_bump,
+    )]
+    pub insurance_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [SEED_MINT_ACCOUNT],
+        bump,
+    )]
+    pub inrc_mint: Account<'info, Mint>,
+
+    /// CHECK: This is a PDA for the mint authority
+    #[account(
+        seeds = [SEED_TREASURY_AUTHORITY],
+        bump = config.treasury_authority_bump,
+    )]
+    pub treasury_authority: AccountInfo<'info>,
+
+    /// CHECK: This is the usdc price feed
+    #[account(
+        address = config.usdc_price_feed,
+    )]
+    pub usdc_price_feed: AccountInfo<'info>,
+
+    #[account(
+        seeds = [SEED_KEEPER_FEED, config.usdc_mint.as_ref()],
+        bump = usdc_keeper_feed.bump,
+    )]
+    pub usdc_keeper_feed: Option<Account<'info, KeeperFeed>>,
+
+    pub token_program: Program<'info, Token>,
+}
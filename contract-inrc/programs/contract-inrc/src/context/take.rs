@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    Auction, CollateralType, Config, KeeperFeed, ErrorCode, ProtocolState, SEED_AUCTION, SEED_COLLATERAL_TYPE, SEED_CONFIG_ACCOUNT, SEED_MINT_ACCOUNT,
+    SEED_INSURANCE_VAULT, SEED_PROTOCOL_STATE, SEED_TREASURY_AUTHORITY, SEED_KEEPER_FEED,
+};
+
+#[derive(Accounts)]
+pub struct Take<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG_ACCOUNT],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_TYPE, collateral_mint.key().as_ref()],
+        bump = collateral_type.bump,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+
+    /// CHECK: receives whatever collateral is left
+    /// once the auction's debt is covered
+    #[account(
+        address = auction.owner,
+    )]
+    pub position_owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_AUCTION, position_owner.key().as_ref(), &auction.position_id.to_le_bytes()],
+        bump = auction.bump,
+        has_one = collateral_mint @ ErrorCode::CollateralTypeMismatch,
+        has_one = payer @ ErrorCode::AuctionPayerMismatch,
+    )]
+    pub auction: Account<'info, Auction>,
+
+    /// CHECK: paid for the auction account in start_auction, its rent goes
+    /// back here when the auction closes
+    #[account(mut)]
+    pub payer: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_MINT_ACCOUNT],
+        bump,
+    )]
+    pub inrc_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = inrc_mint,
+        associated_token::authority = bidder,
+    )]
+    pub bidder_inrc_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = bidder,
+    )]
+    pub bidder_collateral_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = position_owner,
+    )]
+    pub owner_collateral_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA for the mint authority
+    #[account(
+        seeds = [SEED_TREASURY_AUTHORITY],
+        bump = config.treasury_authority_bump,
+    )]
+    pub treasury_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = treasury_authority,
+    )]
+    pub collateral_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [SEED_INSURANCE_VAULT],
+        bump = config.insurance_vault_bump,
+    )]
+    pub insurance_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: This is the usdc price feed
+    #[account(
+        address = config.usdc_price_feed,
+    )]
+    pub usdc_price_feed: AccountInfo<'info>,
+
+    #[account(
+        seeds = [SEED_KEEPER_FEED, config.usdc_mint.as_ref()],
+        bump = usdc_keeper_feed.bump,
+    )]
+    pub usdc_keeper_feed: Option<Account<'info, KeeperFeed>>,
+
+    #[account(
+        mut,
+        token::mint = config.usdc_mint,
+        token::authority = bidder,
+    )]
+    pub bidder_usdc_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is a price feed
+    #[account(
+        address = collateral_type.price_feed,
+    )]
+    pub price_feed: AccountInfo<'info>,
+
+    #[account(
+        seeds = [SEED_KEEPER_FEED, collateral_type.mint.as_ref()],
+        bump = keeper_feed.bump,
+    )]
+    pub keeper_feed: Option<Account<'info, KeeperFeed>>,
+
+    pub collateral_mint: Account<'info, Mint>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+use crate::{ErrorCode, KeeperFeed, SEED_KEEPER_FEED};
+
+#[derive(Accounts)]
+pub struct UpdateKeeperFeed<'info> {
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_KEEPER_FEED, keeper_feed.mint.as_ref()],
+        bump = keeper_feed.bump,
+        has_one = keeper @ ErrorCode::Unauthorized,
+    )]
+    pub keeper_feed: Account<'info, KeeperFeed>,
+}
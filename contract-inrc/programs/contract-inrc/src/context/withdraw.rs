@@ -3,19 +3,41 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 use anchor_spl::associated_token::AssociatedToken;
 
 use crate::{
-    Config, UserCollateral, SEED_CONFIG_ACCOUNT, SEED_MINT_ACCOUNT, SEED_TREASURY_AUTHORITY, SEED_COLLATERAL_ACCOUNT, USDC_INR_FEED_ID_BYTES,
+    CollateralType, SortedPositions, Config, KeeperFeed, ProtocolState, ErrorCode, UserCollateral, SEED_CONFIG_ACCOUNT, SEED_PROTOCOL_STATE, SEED_FEE_VAULT, SEED_MINT_ACCOUNT, SEED_TREASURY_AUTHORITY, SEED_COLLATERAL_ACCOUNT, SEED_COLLATERAL_TYPE, SEED_KEEPER_FEED,
 };
 
 #[derive(Accounts)]
+#[instruction(position_id: u64)]
 pub struct BurnInrcAndWithdrawUsdc<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
     #[account(
+        mut,
         seeds = [SEED_CONFIG_ACCOUNT],
         bump,
     )]
     pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL_STATE],
+        bump = protocol_state.bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_TYPE, collateral_mint.key().as_ref()],
+        bump = collateral_type.bump,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+
+    #[account(
+        mut,
+        address = collateral_type.sorted_positions,
+    )]
+    pub sorted_positions: AccountLoader<'info, SortedPositions>,
     
     #[account(
         mut,
@@ -26,10 +48,10 @@ pub struct BurnInrcAndWithdrawUsdc<'info> {
 
     #[account(
         mut,
-        associated_token::mint = usdc_mint,
+        associated_token::mint = collateral_mint,
         associated_token::authority = signer,
     )]
-    pub user_usdc_account: Account<'info, TokenAccount>,
+    pub user_collateral_account: Account<'info, TokenAccount>,
 
     /// CHECK: This is a PDA for the mint authority
     #[account(
@@ -40,17 +62,18 @@ pub struct BurnInrcAndWithdrawUsdc<'info> {
 
     #[account(
         mut,
-        seeds = [SEED_COLLATERAL_ACCOUNT, signer.key().as_ref()], 
-        bump,
+        seeds = [SEED_COLLATERAL_ACCOUNT, signer.key().as_ref(), &position_id.to_le_bytes()], 
+        bump = user_collateral.bump,
+        has_one = collateral_mint @ ErrorCode::CollateralTypeMismatch,
     )]
     pub user_collateral: Account<'info, UserCollateral>,
 
     #[account(
         mut,
-        associated_token::mint = usdc_mint,
+        associated_token::mint = collateral_mint,
         associated_token::authority = treasury_authority,
     )]
-    pub usdc_treasury_account: Account<'info, TokenAccount>,
+    pub collateral_vault: Account<'info, TokenAccount>,
 
     #[account(
         mut,
@@ -59,13 +82,26 @@ pub struct BurnInrcAndWithdrawUsdc<'info> {
     )]
     pub user_inrc_account: Account<'info, TokenAccount>,
 
+    #[account(
+        mut,
+        seeds = [SEED_FEE_VAULT],
+        bump = config.fee_vault_bump,
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
     /// CHECK: This is a price feed
     #[account(
-        address = Pubkey::new_from_array(USDC_INR_FEED_ID_BYTES),
+        address = collateral_type.price_feed,
+    )]
+    pub price_feed: AccountInfo<'info>,
+
+    #[account(
+        seeds = [SEED_KEEPER_FEED, collateral_type.mint.as_ref()],
+        bump = keeper_feed.bump,
     )]
-    pub usdc_inr_price_feed: AccountInfo<'info>,
+    pub keeper_feed: Option<Account<'info, KeeperFeed>>,
 
-    pub usdc_mint: Account<'info, Mint>,
+    pub collateral_mint: Account<'info, Mint>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
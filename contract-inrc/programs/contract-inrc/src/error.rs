@@ -20,4 +20,80 @@ pub enum ErrorCode {
     InsufficientFunds,
     #[msg("Price Overflow")]
     ArithmeticOverflow,
+    #[msg("Invalid risk parameters")]
+    InvalidRiskParameters,
+    #[msg("Config update is still timelocked")]
+    ConfigUpdateTimelocked,
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+    #[msg("Minting is paused")]
+    MintPaused,
+    #[msg("Withdrawals are paused")]
+    WithdrawPaused,
+    #[msg("Liquidations are paused")]
+    LiquidationPaused,
+    #[msg("Deposits are paused")]
+    DepositPaused,
+    #[msg("Repayments are paused")]
+    RepayPaused,
+    #[msg("Collateral does not match the position's collateral type")]
+    CollateralTypeMismatch,
+    #[msg("Debt ceiling exceeded")]
+    DebtCeilingExceeded,
+    #[msg("Portfolio cannot hold more collateral types")]
+    PortfolioFull,
+    #[msg("Remaining accounts do not match the portfolio balances")]
+    InvalidPortfolioAccounts,
+    #[msg("Protocol debt ceiling exceeded")]
+    GlobalDebtCeilingExceeded,
+    #[msg("Position debt is below the minimum")]
+    BelowMinPositionDebt,
+    #[msg("Too many collateral types registered")]
+    TooManyCollateralTypes,
+    #[msg("Not allowed in the current liquidation mode")]
+    LiquidationModeMismatch,
+    #[msg("Auction cannot be reset yet")]
+    AuctionNotExpired,
+    #[msg("Auction price is above the bid limit")]
+    AuctionPriceTooHigh,
+    #[msg("Position is not underwater")]
+    PositionNotUnderwater,
+    #[msg("Insurance draw is above the given maximum")]
+    InsuranceDrawTooHigh,
+    #[msg("Not enough inrc in the stability pool")]
+    InsufficientStabilityPoolDeposits,
+    #[msg("Invalid stability pool epoch account")]
+    InvalidStabilityPoolEpoch,
+    #[msg("PSM capacity exceeded")]
+    PsmCapacityExceeded,
+    #[msg("Not enough usdc in the PSM reserve")]
+    InsufficientPsmReserve,
+    #[msg("Swap output below the minimum")]
+    SlippageExceeded,
+    #[msg("Positions have to be redeemed in order, lowest health factor first")]
+    InvalidRedemptionOrder,
+    #[msg("Price update is for a different feed")]
+    PriceFeedIdMismatch,
+    #[msg("Price update is not fully verified")]
+    PriceNotFullyVerified,
+    #[msg("Price confidence band is too wide")]
+    PriceConfidenceTooWide,
+    #[msg("Price sources disagree by more than the allowed deviation")]
+    OracleDeviationTooHigh,
+    #[msg("The keeper feed of this asset has to be passed")]
+    KeeperFeedRequired,
+    #[msg("A keeper price can't be used for this action")]
+    KeeperPriceNotAllowed,
+    #[msg("Price history does not cover the twap window")]
+    PriceHistoryUnavailable,
+    #[msg("Price was recorded too recently")]
+    PriceObservationTooSoon,
+    #[msg("Price is older than the maximum age")]
+    PriceStale,
+    #[msg("Account did not pay for the auction")]
+    AuctionPayerMismatch,
+    #[msg("Sorted positions list is full")]
+    SortedPositionsFull,
+    #[msg("Expected four remaining accounts for every portfolio balance")]
+    PortfolioAccountCountMismatch,
 }
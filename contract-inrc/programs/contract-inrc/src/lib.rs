@@ -2,10 +2,12 @@ pub mod constant;
 pub mod states;
 pub mod context;
 pub mod error;
+pub mod oracle;
 
 pub use states::*;
 pub use context::*;
 pub use constant::*;
+pub use oracle::*;
 
 
 use anchor_lang::prelude::*;
@@ -13,7 +15,6 @@ use anchor_spl::token;
 use anchor_spl::token::Burn;
 use anchor_spl::token::Transfer;
 use anchor_spl::token::{MintTo, mint_to};
-use pyth_sdk_solana::state::SolanaPriceAccount; 
 
 
 declare_id!("FNKmejvZ2Gx3Rjut2MKoqxcz8M8HToMiQnazjDtMcYRY");
@@ -23,163 +24,2254 @@ declare_id!("FNKmejvZ2Gx3Rjut2MKoqxcz8M8HToMiQnazjDtMcYRY");
 pub mod contract_inrc {
     use super::*;
 
-    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+    pub fn initialize_config(ctx: Context<InitializeConfig>, params: InitConfigParams) -> Result<()> {
         ctx.accounts.config.authority = ctx.accounts.signer.key();
+        ctx.accounts.config.guardian = ctx.accounts.signer.key();
         ctx.accounts.config.inrc_mint = ctx.accounts.inrc_mint.key();
         ctx.accounts.config.usdc_mint = ctx.accounts.usdc_mint.key();
+        ctx.accounts.config.usdc_decimals = ctx.accounts.usdc_mint.decimals;
+        ctx.accounts.config.usdc_price_feed = params.usdc_price_feed;
+        ctx.accounts.config.usdc_price_feed_id = params.usdc_price_feed_id;
+        ctx.accounts.config.usdc_keeper_feed = params.usdc_keeper_feed;
         ctx.accounts.config.treasury_authority = ctx.accounts.treasury_authority.key();
         ctx.accounts.config.bump = ctx.bumps.config;
         ctx.accounts.config.treasury_authority_bump = ctx.bumps.treasury_authority;
         ctx.accounts.config.mint_pda_bump = ctx.bumps.inrc_mint;
+        ctx.accounts.protocol_state.bump = ctx.bumps.protocol_state;
+        ctx.accounts.config.fee_vault_bump = ctx.bumps.fee_vault;
+        ctx.accounts.config.insurance_vault_bump = ctx.bumps.insurance_vault;
+        ctx.accounts.config.config_update_delay = params.config_update_delay;
+        ctx.accounts.config.stability_fee_rate = params.stability_fee_rate;
+        ctx.accounts.config.mint_fee_bps = params.mint_fee_bps;
+        ctx.accounts.config.redeem_fee_bps = params.redeem_fee_bps;
+        ctx.accounts.config.debt_ceiling = params.debt_ceiling;
+        ctx.accounts.config.min_position_debt = params.min_position_debt;
+        ctx.accounts.config.close_factor_bps = params.close_factor_bps;
+        ctx.accounts.config.full_liquidation_health_factor = params.full_liquidation_health_factor;
+        ctx.accounts.config.liquidation_dust = params.liquidation_dust;
+        ctx.accounts.config.liquidation_mode = params.liquidation_mode;
+        ctx.accounts.config.auction_start_discount_bps = params.auction_start_discount_bps;
+        ctx.accounts.config.auction_discount_bps_per_second = params.auction_discount_bps_per_second;
+        ctx.accounts.config.auction_max_discount_bps = params.auction_max_discount_bps;
+        ctx.accounts.config.auction_duration = params.auction_duration;
+        ctx.accounts.config.insurance_share_bps = params.insurance_share_bps;
+        ctx.accounts.config.psm_fee_in_bps = params.psm_fee_in_bps;
+        ctx.accounts.config.psm_fee_out_bps = params.psm_fee_out_bps;
+        ctx.accounts.config.psm_capacity = params.psm_capacity;
+        ctx.accounts.config.redemption_fee_bps = params.redemption_fee_bps;
+        ctx.accounts.config.max_price_confidence_bps = params.max_price_confidence_bps;
+        ctx.accounts.config.max_oracle_deviation_bps = params.max_oracle_deviation_bps;
+        ctx.accounts.config.liquidation_price_mode = params.liquidation_price_mode;
+        ctx.accounts.config.auction_price_mode = params.auction_price_mode;
+        ctx.accounts.config.twap_window = params.twap_window;
+        ctx.accounts.config.cumulative_rate_index = RATE_PRECISION;
+        ctx.accounts.config.last_accrual_timestamp = Clock::get()?.unix_timestamp;
+        ctx.accounts.config.validate_risk_params()?;
         msg!("Initializing config");
         
         Ok(())
     }
 
-    pub fn deposit_usdc_and_mint_inrc(ctx: Context<DepositUsdcAndMintInrc>, amount_usdc: u64) -> Result<()> {
+    pub fn propose_config_update(ctx: Context<ProposeConfigUpdate>, params: ConfigUpdateParams) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let config = &ctx.accounts.config;
+        let pending_config_update = &mut ctx.accounts.pending_config_update;
+
+        // check the proposal against the config it would produce so a bad
+        // update is rejected now rather than after the delay
+        let mut updated_config = (**config).clone();
+        updated_config.apply_update(&params);
+        updated_config.validate_risk_params()?;
+
+        pending_config_update.params = params;
+        pending_config_update.proposed_at = clock.unix_timestamp;
+        pending_config_update.executable_at = clock.unix_timestamp
+            .checked_add(config.config_update_delay)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        pending_config_update.bump = ctx.bumps.pending_config_update;
+        msg!("Config update executable at {}", pending_config_update.executable_at);
+
+        Ok(())
+    }
+
+    pub fn execute_config_update(ctx: Context<ExecuteConfigUpdate>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let pending_config_update = &ctx.accounts.pending_config_update;
+        let clock = Clock::get()?;
+
+        if clock.unix_timestamp < pending_config_update.executable_at {
+            return err!(ErrorCode::ConfigUpdateTimelocked);
+        }
+
+        // settle interest at the old rate before a new one takes effect
+        config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+        config.apply_update(&pending_config_update.params);
+        config.validate_risk_params()?;
+        msg!("Config update executed");
+
+        Ok(())
+    }
+
+    pub fn cancel_config_update(ctx: Context<CancelConfigUpdate>) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        msg!("Config update cancelled");
+
+        Ok(())
+    }
+
+    // nominating Pubkey::default() withdraws a pending nomination
+    pub fn nominate_authority(ctx: Context<NominateAuthority>, new_authority: Pubkey) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        ctx.accounts.config.pending_authority = new_authority;
+        msg!("Authority nominated: {}", new_authority);
+
+        Ok(())
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let config = &mut ctx.accounts.config;
+
+        config.authority = config.pending_authority;
+        config.pending_authority = Pubkey::default();
+        msg!("Authority transferred to {}", config.authority);
+
+        Ok(())
+    }
+
+    pub fn renounce_authority(ctx: Context<RenounceAuthority>) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let config = &mut ctx.accounts.config;
+
+        config.authority = Pubkey::default();
+        config.pending_authority = Pubkey::default();
+        msg!("Authority renounced");
+
+        Ok(())
+    }
+
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        ctx.accounts.config.guardian = guardian;
+        msg!("Guardian set to {}", guardian);
+
+        Ok(())
+    }
+
+    pub fn set_paused_flags(ctx: Context<SetPausedFlags>, paused_flags: u8) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        if paused_flags & !PAUSE_ALL != 0 {
+            return err!(ErrorCode::InvalidPauseFlags);
+        }
+
+        ctx.accounts.config.paused_flags = paused_flags;
+        msg!("Paused flags set to {:#010b}", paused_flags);
+
+        Ok(())
+    }
+
+    pub fn collect_fees(ctx: Context<CollectFees>, amount_inrc: u64) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
         let config = & ctx.accounts.config;
-        let user_collateral = &mut ctx.accounts.user_collateral;
+
+        if amount_inrc == 0 {
+            return err!(ErrorCode::InvalidAmount);
+        }
+
+        // the insurance share stays in the vault until it is swept
+        if ctx.accounts.fee_vault.amount.saturating_sub(config.insurance_fees) < amount_inrc {
+            return err!(ErrorCode::InsufficientFunds);
+        }
+
+        let transfer_cpi_account = Transfer {
+            from: ctx.accounts.fee_vault.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.treasury_authority.to_account_info(),
+        };
+
+        let treasury_seeds = &[SEED_TREASURY_AUTHORITY,&[config.treasury_authority_bump]];
+        let signer_seeds = &[&treasury_seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_cpi_account,
+                signer_seeds,
+            ),
+            amount_inrc
+        )?;
+        msg!("Collected {} inrc in fees", amount_inrc);
+
+        Ok(())
+    }
+
+    pub fn seed_insurance(ctx: Context<SeedInsurance>, amount_usdc: u64) -> Result<()> {
         let clock = Clock::get()?;
-        
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
 
         if amount_usdc == 0 {
             return err!(ErrorCode::InvalidAmount);
         }
 
-        if user_collateral.depositor == Pubkey::default() {
-            user_collateral.depositor = ctx.accounts.signer.key();
-            user_collateral.bump = ctx.bumps.user_collateral;
-            user_collateral.usdc_deposit = 0;
-            user_collateral.inrc_minted = 0;
-            msg!("User collateral account created for {}", user_collateral.depositor);
-        }else if  user_collateral.depositor != ctx.accounts.signer.key() {
-            return err!(ErrorCode::Unauthorized);
+        let cpi_account = Transfer {
+            from: ctx.accounts.authority_usdc_account.to_account_info(),
+            to: ctx.accounts.insurance_vault.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_account,
+            ),
+            amount_usdc
+        )?;
+        msg!("Insurance vault seeded with {} usdc", amount_usdc);
+
+        Ok(())
+    }
+
+    pub fn get_insurance_balance(ctx: Context<GetInsuranceBalance>) -> Result<u64> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let balance = ctx.accounts.insurance_vault.amount;
+        msg!("Insurance vault holds {} usdc", balance);
+
+        Ok(balance)
+    }
+
+    // anyone can move the insurance share of fees into the insurance vault.
+    // the inrc is burned out of the fee vault and the same value in usdc
+    // comes out of the psm reserve at the oracle rate
+    pub fn sweep_insurance_fees(ctx: Context<SweepInsuranceFees>, amount_inrc: u64) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let config = &mut ctx.accounts.config;
+        let psm = &mut ctx.accounts.psm;
+
+        if amount_inrc == 0 || amount_inrc > config.insurance_fees {
+            return err!(ErrorCode::InvalidAmount);
         }
 
-        let usdc_inr_price = get_pyth_price(&ctx.accounts.usdc_inr_price_feed.to_account_info(), clock.unix_timestamp, MAX_AGE, TARGET_PRICE_DECIMALS)?;
+        let usdc_price = get_price(&ctx.accounts.usdc_price_feed.to_account_info(), &config.usdc_price_feed_id, recorded_keeper_feed(ctx.accounts.usdc_keeper_feed.as_deref(), &config.usdc_keeper_feed)?, clock.unix_timestamp, config)?.price;
+        let amount_usdc = inrc_value_in_collateral(amount_inrc, config.usdc_decimals, usdc_price)?;
 
-        if ctx.accounts.user_usdc_account.amount < amount_usdc {
-            return err!(ErrorCode::InsufficientFunds);
+        if amount_usdc > ctx.accounts.psm_reserve.amount {
+            return err!(ErrorCode::InsufficientPsmReserve);
         }
-        
-        let total_usdc_after_deposit = user_collateral.usdc_deposit.checked_add(amount_usdc).ok_or(ErrorCode::ArithmeticOverflow)?;
 
-        let total_inrc_value_after_deposit = (total_usdc_after_deposit as u128)
-        .checked_mul(usdc_inr_price )
-        .ok_or(ErrorCode::ArithmeticOverflow)?;
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let treasury_seeds = &[SEED_TREASURY_AUTHORITY,&[config.treasury_authority_bump]];
+        let signer_seeds = &[&treasury_seeds[..]];
 
-    // min health factor here is 120 .One
-    // should have 120% of required usdc for
-    // minting the token
-        let max_inrc_value_in_target_decimals = total_inrc_value_after_deposit
-            .checked_mul(100)
-            .ok_or(ErrorCode::ArithmeticOverflow)?
-            .checked_div(config.min_health_factor as u128)
-            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        let burn_accounts = Burn {
+            mint: ctx.accounts.inrc_mint.to_account_info(),
+            from: ctx.accounts.fee_vault.to_account_info(),
+            authority: ctx.accounts.treasury_authority.to_account_info(),
+        };
 
-        //making sure the decimals are same
-        let max_inrc_to_mint = max_inrc_value_in_target_decimals
-            .checked_div(10u128.pow((TARGET_PRICE_DECIMALS - MINT_DECIMAL as i32) as u32))
-            .ok_or(ErrorCode::ArithmeticOverflow)?
-            as u64;
+        token::burn(
+            CpiContext::new_with_signer(
+                cpi_program.clone(),
+                burn_accounts,
+                signer_seeds,
+            ),
+            amount_inrc
+        )?;
+
+        let transfer_cpi_account = Transfer {
+            from: ctx.accounts.psm_reserve.to_account_info(),
+            to: ctx.accounts.insurance_vault.to_account_info(),
+            authority: ctx.accounts.treasury_authority.to_account_info(),
+        };
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                cpi_program.clone(),
+                transfer_cpi_account,
+                signer_seeds,
+            ),
+            amount_usdc
+        )?;
+
+        config.insurance_fees -= amount_inrc;
+        psm.minted_inrc = psm.minted_inrc.saturating_sub(amount_inrc);
+        msg!("Swept {} inrc of fees into {} usdc of insurance", amount_inrc, amount_usdc);
+
+        Ok(())
+    }
+
+    pub fn register_collateral_type(ctx: Context<RegisterCollateralType>, params: RegisterCollateralTypeParams) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let collateral_type = &mut ctx.accounts.collateral_type;
+
+        collateral_type.mint = ctx.accounts.collateral_mint.key();
+        collateral_type.price_feed = params.price_feed;
+        collateral_type.price_feed_id = params.price_feed_id;
+        collateral_type.keeper_feed = params.keeper_feed;
+        collateral_type.decimals = ctx.accounts.collateral_mint.decimals;
+        collateral_type.liquidation_threshold = params.liquidation_threshold;
+        collateral_type.liquidation_bonus = params.liquidation_bonus;
+        collateral_type.min_health_factor = params.min_health_factor;
+        collateral_type.debt_ceiling = params.debt_ceiling;
+        collateral_type.total_normalized_debt = 0;
+        collateral_type.sorted_positions = ctx.accounts.sorted_positions.key();
+        collateral_type.bump = ctx.bumps.collateral_type;
+        ctx.accounts.sorted_positions.load_init()?.collateral_mint = collateral_type.mint;
+        collateral_type.validate_risk_params(ctx.accounts.config.min_position_debt)?;
+        ctx.accounts.protocol_state.add_collateral_type(collateral_type.mint, collateral_type.decimals)?;
+        msg!("Collateral type registered for {}", collateral_type.mint);
+
+        Ok(())
+    }
+
+    pub fn propose_collateral_type_update(ctx: Context<ProposeCollateralTypeUpdate>, params: CollateralTypeUpdateParams) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
 
-        let inrc_to_mint = max_inrc_to_mint.
-            checked_sub(user_collateral.inrc_minted )
+        let config = &ctx.accounts.config;
+        let collateral_type = &ctx.accounts.collateral_type;
+        let pending_collateral_type_update = &mut ctx.accounts.pending_collateral_type_update;
+
+        let mut updated_collateral_type = (**collateral_type).clone();
+        updated_collateral_type.apply_update(&params);
+        updated_collateral_type.validate_risk_params(ctx.accounts.config.min_position_debt)?;
+
+        pending_collateral_type_update.collateral_mint = collateral_type.mint;
+        pending_collateral_type_update.params = params;
+        pending_collateral_type_update.proposed_at = clock.unix_timestamp;
+        pending_collateral_type_update.executable_at = clock.unix_timestamp
+            .checked_add(config.config_update_delay)
             .ok_or(ErrorCode::ArithmeticOverflow)?;
+        pending_collateral_type_update.bump = ctx.bumps.pending_collateral_type_update;
+        msg!("Collateral type update executable at {}", pending_collateral_type_update.executable_at);
+
+        Ok(())
+    }
+
+    pub fn execute_collateral_type_update(ctx: Context<ExecuteCollateralTypeUpdate>) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let collateral_type = &mut ctx.accounts.collateral_type;
+        let pending_collateral_type_update = &ctx.accounts.pending_collateral_type_update;
 
-        //transfer usdc from user to treasury
+        if clock.unix_timestamp < pending_collateral_type_update.executable_at {
+            return err!(ErrorCode::ConfigUpdateTimelocked);
+        }
+
+        collateral_type.apply_update(&pending_collateral_type_update.params);
+        collateral_type.validate_risk_params(ctx.accounts.config.min_position_debt)?;
+        msg!("Collateral type update executed for {}", collateral_type.mint);
+
+        Ok(())
+    }
+
+    pub fn cancel_collateral_type_update(ctx: Context<CancelCollateralTypeUpdate>) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        msg!("Collateral type update cancelled");
+
+        Ok(())
+    }
+
+    pub fn open_position(ctx: Context<OpenPosition>, position_id: u64) -> Result<()> {
+        let user_collateral = &mut ctx.accounts.user_collateral;
+
+        user_collateral.depositor = ctx.accounts.signer.key();
+        user_collateral.position_id = position_id;
+        user_collateral.collateral_mint = ctx.accounts.collateral_mint.key();
+        user_collateral.collateral_amount = 0;
+        user_collateral.normalized_debt = 0;
+        user_collateral.bump = ctx.bumps.user_collateral;
+        msg!("Position {} opened for {}", position_id, user_collateral.depositor);
+
+        Ok(())
+    }
+
+    pub fn deposit_collateral(ctx: Context<DepositCollateral>, _position_id: u64, amount_collateral: u64) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let config = & ctx.accounts.config;
+        let collateral_type = & ctx.accounts.collateral_type;
+        let user_collateral = &mut ctx.accounts.user_collateral;
+        let sort_key_before = SortedPositions::sort_key(user_collateral, collateral_type.decimals);
+
+        if config.is_paused(PAUSE_DEPOSIT) {
+            return err!(ErrorCode::DepositPaused);
+        }
+
+        if amount_collateral == 0 {
+            return err!(ErrorCode::InvalidAmount);
+        }
+
+        if ctx.accounts.user_collateral_account.amount < amount_collateral {
+            return err!(ErrorCode::InsufficientFunds);
+        }
 
         let cpi_account = Transfer {
-            from: ctx.accounts.user_usdc_account.to_account_info(),
-            to: ctx.accounts.usdc_treasury_account.to_account_info(),
+            from: ctx.accounts.user_collateral_account.to_account_info(),
+            to: ctx.accounts.collateral_vault.to_account_info(),
             authority: ctx.accounts.signer.to_account_info(),
         };
-        
-        let cpi_program = ctx.accounts.token_program.to_account_info();
 
         token::transfer(
             CpiContext::new(
-                cpi_program.clone(),
-                cpi_account,             
-                ),
-                amount_usdc
+                ctx.accounts.token_program.to_account_info(),
+                cpi_account,
+            ),
+            amount_collateral
+        )?;
+
+        user_collateral.collateral_amount = user_collateral.collateral_amount.checked_add(amount_collateral).ok_or(ErrorCode::ArithmeticOverflow)?;
+        ctx.accounts.protocol_state.credit_collateral(&user_collateral.collateral_mint, amount_collateral)?;
+        ctx.accounts.sorted_positions.load_mut()?.update(user_collateral, sort_key_before, collateral_type.decimals)?;
+
+        Ok(())
+    }
+
+    pub fn mint_inrc(ctx: Context<MintInrc>, _position_id: u64, amount_inrc: u64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        let collateral_type = &mut ctx.accounts.collateral_type;
+        let user_collateral = &mut ctx.accounts.user_collateral;
+        let sort_key_before = SortedPositions::sort_key(user_collateral, collateral_type.decimals);
+        let clock = Clock::get()?;
+
+        if config.is_paused(PAUSE_MINT) {
+            return err!(ErrorCode::MintPaused);
+        }
+
+        if amount_inrc == 0 {
+            return err!(ErrorCode::InvalidAmount);
+        }
+
+        config.accrue_stability_fee(protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        // the mint fee is added to the position's debt and minted to the fee vault
+        let mint_fee = fee_amount(amount_inrc, config.mint_fee_bps)?;
+        let normalized_amount = config.normalize_debt(amount_inrc.checked_add(mint_fee).ok_or(ErrorCode::ArithmeticOverflow)?)?;
+        let total_normalized_debt_after_mint = collateral_type.total_normalized_debt.checked_add(normalized_amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        if config.debt_from_normalized(total_normalized_debt_after_mint)? > collateral_type.debt_ceiling {
+            return err!(ErrorCode::DebtCeilingExceeded);
+        }
+
+        let protocol_normalized_debt_after_mint = protocol_state.total_normalized_debt.checked_add(normalized_amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        if config.debt_from_normalized(protocol_normalized_debt_after_mint)? > config.debt_ceiling {
+            return err!(ErrorCode::GlobalDebtCeilingExceeded);
+        }
+
+        let collateral_price = get_price(&ctx.accounts.price_feed.to_account_info(), &collateral_type.price_feed_id, recorded_keeper_feed(ctx.accounts.keeper_feed.as_deref(), &collateral_type.keeper_feed)?, clock.unix_timestamp, config)?.borrow_price()?;
+
+        let normalized_debt_after = user_collateral.normalized_debt.checked_add(normalized_amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+        config.check_position_debt(normalized_debt_after)?;
+
+        // new debt has to be opened at or above the min health factor
+        let health_factor_after_mint = health_factor(
+            collateral_value_in_inrc(user_collateral.collateral_amount, collateral_type.decimals, collateral_price)?,
+            config.debt_from_normalized(normalized_debt_after)?,
+        )?;
+
+        if health_factor_after_mint < collateral_type.min_health_factor as u128 {
+            return err!(ErrorCode::BelowMinHealthFactor);
+        }
+
+        let mint_to_account = MintTo {
+            mint: ctx.accounts.inrc_mint.to_account_info(),
+            to: ctx.accounts.user_inrc_account.to_account_info(),
+            authority: ctx.accounts.treasury_authority.to_account_info(),
+        };
+
+        let treasury_seeds = &[SEED_TREASURY_AUTHORITY,&[config.treasury_authority_bump]];
+        let signer_seeds = &[&treasury_seeds[..]];
+
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                mint_to_account,
+                signer_seeds,
+            ),
+            amount_inrc
         )?;
 
-        // mint inrc to user ata
+        if mint_fee > 0 {
+            let fee_mint_to_account = MintTo {
+                mint: ctx.accounts.inrc_mint.to_account_info(),
+                to: ctx.accounts.fee_vault.to_account_info(),
+                authority: ctx.accounts.treasury_authority.to_account_info(),
+            };
+
+            mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    fee_mint_to_account,
+                    signer_seeds,
+                ),
+                mint_fee
+            )?;
+        }
+
+        config.reserve_insurance_fee(mint_fee)?;
+
+        user_collateral.normalized_debt = normalized_debt_after;
+        collateral_type.total_normalized_debt = total_normalized_debt_after_mint;
+        protocol_state.total_normalized_debt = protocol_normalized_debt_after_mint;
+        ctx.accounts.sorted_positions.load_mut()?.update(user_collateral, sort_key_before, collateral_type.decimals)?;
+
+        Ok(())
+    }
+
+    pub fn repay_inrc(ctx: Context<RepayInrc>, _position_id: u64, amount_inrc: u64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        let collateral_type = &mut ctx.accounts.collateral_type;
+        let user_collateral = &mut ctx.accounts.user_collateral;
+        let sort_key_before = SortedPositions::sort_key(user_collateral, collateral_type.decimals);
+        let clock = Clock::get()?;
+
+        if config.is_paused(PAUSE_REPAY) {
+            return err!(ErrorCode::RepayPaused);
+        }
+
+        if amount_inrc == 0 {
+            return err!(ErrorCode::InvalidAmount);
+        }
+
+        config.accrue_stability_fee(protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let normalized_repayment = config.normalized_repayment(user_collateral.normalized_debt, amount_inrc)?;
+        config.check_position_debt(user_collateral.normalized_debt.checked_sub(normalized_repayment).ok_or(ErrorCode::ArithmeticOverflow)?)?;
+        let redeem_fee = fee_amount(amount_inrc, config.redeem_fee_bps)?;
+
+        let burn_accounts = Burn {
+            mint: ctx.accounts.inrc_mint.to_account_info(),
+            from: ctx.accounts.user_inrc_account.to_account_info(),
+            authority: ctx.accounts.signer.to_account_info(),
+        };
+
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                burn_accounts,
+            ),
+            amount_inrc
+        )?;
+
+        if redeem_fee > 0 {
+            let fee_transfer_account = Transfer {
+                from: ctx.accounts.user_inrc_account.to_account_info(),
+                to: ctx.accounts.fee_vault.to_account_info(),
+                authority: ctx.accounts.signer.to_account_info(),
+            };
+
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    fee_transfer_account,
+                ),
+                redeem_fee
+            )?;
+        }
+
+        config.reserve_insurance_fee(redeem_fee)?;
+
+        user_collateral.normalized_debt = user_collateral.normalized_debt.checked_sub(normalized_repayment).ok_or(ErrorCode::ArithmeticOverflow)?;
+        collateral_type.total_normalized_debt = collateral_type.total_normalized_debt.checked_sub(normalized_repayment).ok_or(ErrorCode::ArithmeticOverflow)?;
+        protocol_state.total_normalized_debt = protocol_state.total_normalized_debt.checked_sub(normalized_repayment).ok_or(ErrorCode::ArithmeticOverflow)?;
+        ctx.accounts.sorted_positions.load_mut()?.update(user_collateral, sort_key_before, collateral_type.decimals)?;
+
+        Ok(())
+    }
+
+    pub fn withdraw_collateral(ctx: Context<WithdrawCollateral>, _position_id: u64, amount_collateral: u64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        let collateral_type = & ctx.accounts.collateral_type;
+        let user_collateral = &mut ctx.accounts.user_collateral;
+        let sort_key_before = SortedPositions::sort_key(user_collateral, collateral_type.decimals);
+        let clock = Clock::get()?;
+
+        if config.is_paused(PAUSE_WITHDRAW) {
+            return err!(ErrorCode::WithdrawPaused);
+        }
+
+        if amount_collateral == 0 {
+            return err!(ErrorCode::InvalidAmount);
+        }
+
+        config.accrue_stability_fee(protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let collateral_amount_after = user_collateral.collateral_amount.checked_sub(amount_collateral).ok_or(ErrorCode::InsufficientFunds)?;
+
+        if user_collateral.normalized_debt > 0 {
+            let collateral_price = get_price(&ctx.accounts.price_feed.to_account_info(), &collateral_type.price_feed_id, recorded_keeper_feed(ctx.accounts.keeper_feed.as_deref(), &collateral_type.keeper_feed)?, clock.unix_timestamp, config)?.borrow_price()?;
+
+            let health_factor_after_withdrawal = health_factor(
+                collateral_value_in_inrc(collateral_amount_after, collateral_type.decimals, collateral_price)?,
+                config.debt_from_normalized(user_collateral.normalized_debt)?,
+            )?;
+
+            if health_factor_after_withdrawal < collateral_type.min_health_factor as u128 {
+                return err!(ErrorCode::BelowMinHealthFactor);
+            }
+        }
+
+        let transfer_cpi_account = Transfer {
+            from: ctx.accounts.collateral_vault.to_account_info(),
+            to: ctx.accounts.user_collateral_account.to_account_info(),
+            authority: ctx.accounts.treasury_authority.to_account_info(),
+        };
+
+        let treasury_seeds = &[SEED_TREASURY_AUTHORITY,&[config.treasury_authority_bump]];
+        let signer_seeds = &[&treasury_seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_cpi_account,
+                signer_seeds,
+            ),
+            amount_collateral
+        )?;
+
+        user_collateral.collateral_amount = collateral_amount_after;
+        protocol_state.debit_collateral(&user_collateral.collateral_mint, amount_collateral)?;
+        ctx.accounts.sorted_positions.load_mut()?.update(user_collateral, sort_key_before, collateral_type.decimals)?;
+
+        Ok(())
+    }
+
+     pub fn burn_inrc_and_withdraw_usdc(ctx: Context<BurnInrcAndWithdrawUsdc>, _position_id: u64, amount_inrc: u64, amount_collateral: u64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        let collateral_type = &mut ctx.accounts.collateral_type;
+        let user_collateral = &mut ctx.accounts.user_collateral;
+        let sort_key_before = SortedPositions::sort_key(user_collateral, collateral_type.decimals);
+        let clock = Clock::get()?;
+        
+        if config.is_paused(PAUSE_WITHDRAW) {
+            return err!(ErrorCode::WithdrawPaused);
+        }
+
+        if amount_inrc == 0 && amount_collateral == 0 {
+            return err!(ErrorCode::InvalidAmount);
+        }
+        
+        config.accrue_stability_fee(protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let normalized_repayment = config.normalized_repayment(user_collateral.normalized_debt, amount_inrc)?;
+        let remaining_normalized_debt = user_collateral.normalized_debt.checked_sub(normalized_repayment).ok_or(ErrorCode::ArithmeticOverflow)?;
+        config.check_position_debt(remaining_normalized_debt)?;
+        let redeem_fee = fee_amount(amount_inrc, config.redeem_fee_bps)?;
+        let remaining_collateral = user_collateral.collateral_amount.checked_sub(amount_collateral).ok_or(ErrorCode::InsufficientFunds)?;
+
+        //the withdrawal is only limited by the health
+        //of what is left in the position
+        if remaining_normalized_debt > 0 && amount_collateral > 0 {
+            let collateral_price = get_price(&ctx.accounts.price_feed.to_account_info(), &collateral_type.price_feed_id, recorded_keeper_feed(ctx.accounts.keeper_feed.as_deref(), &collateral_type.keeper_feed)?, clock.unix_timestamp, config)?.borrow_price()?;
+
+            let health_factor_after_withdrawal = health_factor(
+                collateral_value_in_inrc(remaining_collateral, collateral_type.decimals, collateral_price)?,
+                config.debt_from_normalized(remaining_normalized_debt)?,
+            )?;
+
+            if health_factor_after_withdrawal < collateral_type.min_health_factor as u128 {
+                return err!(ErrorCode::BelowMinHealthFactor);
+            };
+        }
+
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+
+        if amount_inrc > 0 {
+            let burn_accounts = Burn {
+                mint: ctx.accounts.inrc_mint.to_account_info(),
+                from: ctx.accounts.user_inrc_account.to_account_info(),
+                authority: ctx.accounts.signer.to_account_info(),
+            };
+
+            token::burn(
+                CpiContext::new(
+                    cpi_program.clone(),
+                    burn_accounts,
+                ),
+                amount_inrc
+            )?;
+        }
+
+        if redeem_fee > 0 {
+            let fee_transfer_account = Transfer {
+                from: ctx.accounts.user_inrc_account.to_account_info(),
+                to: ctx.accounts.fee_vault.to_account_info(),
+                authority: ctx.accounts.signer.to_account_info(),
+            };
+
+            token::transfer(
+                CpiContext::new(
+                    cpi_program.clone(),
+                    fee_transfer_account,
+                ),
+                redeem_fee
+            )?;
+        }
+
+        config.reserve_insurance_fee(redeem_fee)?;
+
+        if amount_collateral > 0 {
+            let transfer_cpi_account = Transfer {
+                from: ctx.accounts.collateral_vault.to_account_info(),
+                to: ctx.accounts.user_collateral_account.to_account_info(),
+                authority: ctx.accounts.treasury_authority.to_account_info(),
+            };
+
+            let treasury_seeds = &[SEED_TREASURY_AUTHORITY,&[config.treasury_authority_bump]];
+
+            let signer_seeds = &[&treasury_seeds[..]];
+
+            token::transfer(
+                CpiContext::new_with_signer(
+                    cpi_program,
+                    transfer_cpi_account,
+                    signer_seeds,
+                ),
+                amount_collateral
+            )?;
+        }
+        user_collateral.collateral_amount = remaining_collateral;
+        protocol_state.debit_collateral(&user_collateral.collateral_mint, amount_collateral)?;
+        user_collateral.normalized_debt = remaining_normalized_debt;
+        collateral_type.total_normalized_debt = collateral_type.total_normalized_debt.checked_sub(normalized_repayment).ok_or(ErrorCode::ArithmeticOverflow)?;
+        protocol_state.total_normalized_debt = protocol_state.total_normalized_debt.checked_sub(normalized_repayment).ok_or(ErrorCode::ArithmeticOverflow)?;
+        ctx.accounts.sorted_positions.load_mut()?.update(user_collateral, sort_key_before, collateral_type.decimals)?;
+            
+       Ok(())
+    }
+
+    pub fn close_position(ctx: Context<ClosePosition>, _position_id: u64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        let collateral_type = &mut ctx.accounts.collateral_type;
+        let user_collateral = & ctx.accounts.user_collateral;
+        let sort_key_before = SortedPositions::sort_key(user_collateral, collateral_type.decimals);
+        let clock = Clock::get()?;
+
+        if config.is_paused(PAUSE_REPAY) {
+            return err!(ErrorCode::RepayPaused);
+        }
+
+        if config.is_paused(PAUSE_WITHDRAW) {
+            return err!(ErrorCode::WithdrawPaused);
+        }
+
+        config.accrue_stability_fee(protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let inrc_debt = config.debt_from_normalized(user_collateral.normalized_debt)?;
+        let redeem_fee = fee_amount(inrc_debt, config.redeem_fee_bps)?;
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+
+        if inrc_debt > 0 {
+            let burn_accounts = Burn {
+                mint: ctx.accounts.inrc_mint.to_account_info(),
+                from: ctx.accounts.user_inrc_account.to_account_info(),
+                authority: ctx.accounts.signer.to_account_info(),
+            };
+
+            token::burn(
+                CpiContext::new(
+                    cpi_program.clone(),
+                    burn_accounts,
+                ),
+                inrc_debt
+            )?;
+        }
+
+        if redeem_fee > 0 {
+            let fee_transfer_account = Transfer {
+                from: ctx.accounts.user_inrc_account.to_account_info(),
+                to: ctx.accounts.fee_vault.to_account_info(),
+                authority: ctx.accounts.signer.to_account_info(),
+            };
+
+            token::transfer(
+                CpiContext::new(
+                    cpi_program.clone(),
+                    fee_transfer_account,
+                ),
+                redeem_fee
+            )?;
+        }
+
+        config.reserve_insurance_fee(redeem_fee)?;
+
+        if user_collateral.collateral_amount > 0 {
+            let transfer_cpi_account = Transfer {
+                from: ctx.accounts.collateral_vault.to_account_info(),
+                to: ctx.accounts.user_collateral_account.to_account_info(),
+                authority: ctx.accounts.treasury_authority.to_account_info(),
+            };
+
+            let treasury_seeds = &[SEED_TREASURY_AUTHORITY,&[config.treasury_authority_bump]];
+            let signer_seeds = &[&treasury_seeds[..]];
+
+            token::transfer(
+                CpiContext::new_with_signer(
+                    cpi_program,
+                    transfer_cpi_account,
+                    signer_seeds,
+                ),
+                user_collateral.collateral_amount
+            )?;
+        }
+        collateral_type.total_normalized_debt = collateral_type.total_normalized_debt.checked_sub(user_collateral.normalized_debt).ok_or(ErrorCode::ArithmeticOverflow)?;
+        protocol_state.total_normalized_debt = protocol_state.total_normalized_debt.checked_sub(user_collateral.normalized_debt).ok_or(ErrorCode::ArithmeticOverflow)?;
+        protocol_state.debit_collateral(&user_collateral.collateral_mint, user_collateral.collateral_amount)?;
+        if let Some(sort_key) = sort_key_before {
+            ctx.accounts.sorted_positions.load_mut()?.remove(user_collateral.depositor, user_collateral.position_id, sort_key);
+        }
+        msg!("Position {} closed for {}", user_collateral.position_id, user_collateral.depositor);
+
+        Ok(())
+    }
+
+    pub fn liquidate(ctx: Context<Liquidate>, _position_id: u64, amount_inrc_to_burn: u64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        let collateral_type = &mut ctx.accounts.collateral_type;
+        let user_collateral = &mut ctx.accounts.user_collateral;
+        let sort_key_before = SortedPositions::sort_key(user_collateral, collateral_type.decimals);
+        let liquidator = & ctx.accounts.liquidator;
+        let clock = Clock::get()?;
+
+        if config.is_paused(PAUSE_LIQUIDATE) {
+            return err!(ErrorCode::LiquidationPaused);
+        }
+
+        if config.liquidation_mode != LiquidationMode::FixedBonus {
+            return err!(ErrorCode::LiquidationModeMismatch);
+        }
+
+        config.accrue_stability_fee(protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let oracle_price = get_price(&ctx.accounts.price_feed.to_account_info(), &collateral_type.price_feed_id, recorded_keeper_feed(ctx.accounts.keeper_feed.as_deref(), &collateral_type.keeper_feed)?, clock.unix_timestamp, config)?;
+        let collateral_price = oracle_price.price;
+        let health_price = get_price_with_mode(&ctx.accounts.price_feed.to_account_info(), &collateral_type.price_feed_id, recorded_keeper_feed(ctx.accounts.keeper_feed.as_deref(), &collateral_type.keeper_feed)?, ctx.accounts.price_history.as_deref().map(|price_history| &**price_history), clock.unix_timestamp, config, config.liquidation_price_mode)?.high();
+
+        let inrc_debt = config.debt_from_normalized(user_collateral.normalized_debt)?;
+        let health_factor = health_factor(
+            collateral_value_in_inrc(user_collateral.collateral_amount, collateral_type.decimals, health_price)?,
+            inrc_debt,
+        )?;
+
+        if health_factor >= collateral_type.liquidation_threshold as u128 {
+            return err!(ErrorCode::AboveMinHealthFactor);
+        }
+
+        if amount_inrc_to_burn > config.max_liquidation_amount(inrc_debt, health_factor)? {
+            return err!(ErrorCode::LiquidationAmountTooHigh);
+        }
+
+        let normalized_repayment = config.normalized_repayment(user_collateral.normalized_debt, amount_inrc_to_burn)?;
+        config.check_position_debt(user_collateral.normalized_debt.checked_sub(normalized_repayment).ok_or(ErrorCode::ArithmeticOverflow)?)?;
+
+        let collateral_to_liquidator = (inrc_value_in_collateral(amount_inrc_to_burn, collateral_type.decimals, collateral_price)? as u128)
+            .checked_mul(100 + collateral_type.liquidation_bonus as u128) //bonus is applied here
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_div(100) 
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+        as u64;
+
+        if collateral_to_liquidator > user_collateral.collateral_amount {
+            return err!(ErrorCode::InsufficientCollateralForLiquidation);
+        }
+
+        // part of the liquidation penalty is kept for the insurance vault
+        let liquidation_penalty = collateral_to_liquidator - inrc_value_in_collateral(amount_inrc_to_burn, collateral_type.decimals, collateral_price)?;
+        let collateral_to_insurance = config.insurance_share(&user_collateral.collateral_mint, liquidation_penalty);
+
+        let burn_accounts = Burn {
+            from: ctx.accounts.liquidator_inrc_account.to_account_info(),
+            mint: ctx.accounts.inrc_mint.to_account_info(),
+            authority: liquidator.to_account_info(),
+        };
+
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+
+        token::burn(
+            CpiContext::new(
+                cpi_program.clone(),
+                burn_accounts,
+            ),
+            amount_inrc_to_burn
+        )?;
+
+        let transfer_cpi_account = Transfer {
+            from: ctx.accounts.collateral_vault.to_account_info(), 
+            to: ctx.accounts.liquidator_collateral_account.to_account_info(),
+            authority: ctx.accounts.treasury_authority.to_account_info(),
+        };
+
+        let treasury_seeds = &[SEED_TREASURY_AUTHORITY,&[config.treasury_authority_bump]];
+        let signer_seeds = &[&treasury_seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                cpi_program.clone(),
+                transfer_cpi_account,
+                signer_seeds,
+            ),
+            collateral_to_liquidator - collateral_to_insurance
+        )?;
+
+        if collateral_to_insurance > 0 {
+            let insurance_transfer_account = Transfer {
+                from: ctx.accounts.collateral_vault.to_account_info(),
+                to: ctx.accounts.insurance_vault.to_account_info(),
+                authority: ctx.accounts.treasury_authority.to_account_info(),
+            };
+
+            token::transfer(
+                CpiContext::new_with_signer(
+                    cpi_program,
+                    insurance_transfer_account,
+                    signer_seeds,
+                ),
+                collateral_to_insurance
+            )?;
+        }
+
+        user_collateral.collateral_amount = user_collateral.collateral_amount
+        .checked_sub(collateral_to_liquidator)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        protocol_state.debit_collateral(&user_collateral.collateral_mint, collateral_to_liquidator)?;
+
+        user_collateral.normalized_debt = user_collateral.normalized_debt
+        .checked_sub(normalized_repayment)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        collateral_type.total_normalized_debt = collateral_type.total_normalized_debt
+        .checked_sub(normalized_repayment)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        protocol_state.total_normalized_debt = protocol_state.total_normalized_debt
+        .checked_sub(normalized_repayment)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+        ctx.accounts.sorted_positions.load_mut()?.update(user_collateral, sort_key_before, collateral_type.decimals)?;
+        Ok(())
+    }
+
+    // moves all of an unhealthy position's collateral and debt into an
+    // auction. the debt keeps accruing until bidders cover it
+    pub fn start_auction(ctx: Context<StartAuction>, position_id: u64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let collateral_type = & ctx.accounts.collateral_type;
+        let user_collateral = &mut ctx.accounts.user_collateral;
+        let sort_key_before = SortedPositions::sort_key(user_collateral, collateral_type.decimals);
+        let auction = &mut ctx.accounts.auction;
+        let clock = Clock::get()?;
+
+        if config.is_paused(PAUSE_LIQUIDATE) {
+            return err!(ErrorCode::LiquidationPaused);
+        }
+
+        if config.liquidation_mode != LiquidationMode::Auction {
+            return err!(ErrorCode::LiquidationModeMismatch);
+        }
+
+        config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let collateral_price = get_price_with_mode(&ctx.accounts.price_feed.to_account_info(), &collateral_type.price_feed_id, recorded_keeper_feed(ctx.accounts.keeper_feed.as_deref(), &collateral_type.keeper_feed)?, ctx.accounts.price_history.as_deref().map(|price_history| &**price_history), clock.unix_timestamp, config, config.auction_price_mode)?.high();
+
+        let health_factor = health_factor(
+            collateral_value_in_inrc(user_collateral.collateral_amount, collateral_type.decimals, collateral_price)?,
+            config.debt_from_normalized(user_collateral.normalized_debt)?,
+        )?;
+
+        if health_factor >= collateral_type.liquidation_threshold as u128 {
+            return err!(ErrorCode::AboveMinHealthFactor);
+        }
+
+        auction.owner = user_collateral.depositor;
+        auction.payer = ctx.accounts.keeper.key();
+        auction.position_id = position_id;
+        auction.collateral_mint = user_collateral.collateral_mint;
+        auction.collateral_amount = user_collateral.collateral_amount;
+        auction.normalized_debt = user_collateral.normalized_debt;
+        auction.started_at = clock.unix_timestamp;
+        auction.bump = ctx.bumps.auction;
+
+        user_collateral.collateral_amount = 0;
+        user_collateral.normalized_debt = 0;
+        ctx.accounts.sorted_positions.load_mut()?.update(user_collateral, sort_key_before, collateral_type.decimals)?;
+        msg!("Auction started for position {} of {}", position_id, auction.owner);
+
+        Ok(())
+    }
+
+    // buys up to `amount_collateral` from the auction at the oracle price less
+    // the current discount, paying at most `max_inrc` for it. a take that
+    // sells the auction out short also buys insurance usdc against the bad
+    // debt, for at most `max_insurance_inrc`
+    pub fn take(ctx: Context<Take>, amount_collateral: u64, max_inrc: u64, max_insurance_inrc: u64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        let collateral_type = &mut ctx.accounts.collateral_type;
+        let auction = &mut ctx.accounts.auction;
+        let clock = Clock::get()?;
+
+        if config.is_paused(PAUSE_LIQUIDATE) {
+            return err!(ErrorCode::LiquidationPaused);
+        }
+
+        if amount_collateral == 0 {
+            return err!(ErrorCode::InvalidAmount);
+        }
+
+        config.accrue_stability_fee(protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let collateral_price = get_price(&ctx.accounts.price_feed.to_account_info(), &collateral_type.price_feed_id, recorded_keeper_feed(ctx.accounts.keeper_feed.as_deref(), &collateral_type.keeper_feed)?, clock.unix_timestamp, config)?.price;
+        let discount_bps = config.auction_discount_bps(auction.started_at, clock.unix_timestamp);
+        let auction_price = collateral_price
+            .checked_mul((BPS_DENOMINATOR - discount_bps) as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_div(BPS_DENOMINATOR as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        let auction_debt = config.debt_from_normalized(auction.normalized_debt)?;
+        let mut collateral_to_bidder = amount_collateral.min(auction.collateral_amount);
+        let mut inrc_to_pay = u64::try_from(collateral_value_in_inrc(collateral_to_bidder, collateral_type.decimals, auction_price)?)
+            .map_err(|_| ErrorCode::ArithmeticOverflow)?;
+
+        // never sell more than it takes to cover the debt
+        if inrc_to_pay > auction_debt {
+            inrc_to_pay = auction_debt;
+            collateral_to_bidder = inrc_value_in_collateral(auction_debt, collateral_type.decimals, auction_price)?
+                .min(auction.collateral_amount);
+        }
+
+        if inrc_to_pay == 0 {
+            return err!(ErrorCode::InvalidAmount);
+        }
+
+        if inrc_to_pay > max_inrc {
+            return err!(ErrorCode::AuctionPriceTooHigh);
+        }
+
+        let normalized_repayment = config.normalized_repayment(auction.normalized_debt, inrc_to_pay)?;
+
+        let burn_accounts = Burn {
+            mint: ctx.accounts.inrc_mint.to_account_info(),
+            from: ctx.accounts.bidder_inrc_account.to_account_info(),
+            authority: ctx.accounts.bidder.to_account_info(),
+        };
+
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+
+        token::burn(
+            CpiContext::new(
+                cpi_program.clone(),
+                burn_accounts,
+            ),
+            inrc_to_pay
+        )?;
+
+        let transfer_cpi_account = Transfer {
+            from: ctx.accounts.collateral_vault.to_account_info(),
+            to: ctx.accounts.bidder_collateral_account.to_account_info(),
+            authority: ctx.accounts.treasury_authority.to_account_info(),
+        };
+
+        let treasury_seeds = &[SEED_TREASURY_AUTHORITY,&[config.treasury_authority_bump]];
+        let signer_seeds = &[&treasury_seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                cpi_program.clone(),
+                transfer_cpi_account,
+                signer_seeds,
+            ),
+            collateral_to_bidder
+        )?;
+
+        auction.collateral_amount = auction.collateral_amount.checked_sub(collateral_to_bidder).ok_or(ErrorCode::ArithmeticOverflow)?;
+        auction.normalized_debt = auction.normalized_debt.checked_sub(normalized_repayment).ok_or(ErrorCode::ArithmeticOverflow)?;
+        collateral_type.total_normalized_debt = collateral_type.total_normalized_debt.checked_sub(normalized_repayment).ok_or(ErrorCode::ArithmeticOverflow)?;
+        protocol_state.total_normalized_debt = protocol_state.total_normalized_debt.checked_sub(normalized_repayment).ok_or(ErrorCode::ArithmeticOverflow)?;
+        protocol_state.debit_collateral(&auction.collateral_mint, collateral_to_bidder)?;
+        msg!("Auction sold {} collateral for {} inrc", collateral_to_bidder, inrc_to_pay);
+
+        // once the debt is covered the rest of the collateral goes back to the owner
+        if auction.normalized_debt == 0 {
+            let leftover_collateral = auction.collateral_amount;
+
+            if leftover_collateral > 0 {
+                let transfer_cpi_account = Transfer {
+                    from: ctx.accounts.collateral_vault.to_account_info(),
+                    to: ctx.accounts.owner_collateral_account.to_account_info(),
+                    authority: ctx.accounts.treasury_authority.to_account_info(),
+                };
+
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        cpi_program,
+                        transfer_cpi_account,
+                        signer_seeds,
+                    ),
+                    leftover_collateral
+                )?;
+
+                protocol_state.debit_collateral(&auction.collateral_mint, leftover_collateral)?;
+            }
+
+            auction.collateral_amount = 0;
+            auction.close(ctx.accounts.payer.to_account_info())?;
+            msg!("Auction finished");
+        } else if auction.collateral_amount == 0 {
+            // sold out without covering the debt, what is left is bad debt
+            let bad_debt = config.debt_from_normalized(auction.normalized_debt)?;
+
+            collateral_type.total_normalized_debt = collateral_type.total_normalized_debt.checked_sub(auction.normalized_debt).ok_or(ErrorCode::ArithmeticOverflow)?;
+            protocol_state.total_normalized_debt = protocol_state.total_normalized_debt.checked_sub(auction.normalized_debt).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+            // insurance usdc buys back as much of the bad debt as it can before
+            // surplus is used, and only what is left after that is socialized
+            let mut insurance_inrc = 0;
+
+            if bad_debt > 0 && ctx.accounts.insurance_vault.amount > 0 {
+                let usdc_price = get_price(&ctx.accounts.usdc_price_feed.to_account_info(), &config.usdc_price_feed_id, recorded_keeper_feed(ctx.accounts.usdc_keeper_feed.as_deref(), &config.usdc_keeper_feed)?, clock.unix_timestamp, config)?.price;
+                let insurance_usdc;
+                (insurance_inrc, insurance_usdc) = insurance_draw(
+                    ctx.accounts.insurance_vault.amount,
+                    config.usdc_decimals,
+                    usdc_price,
+                    bad_debt,
+                )?;
+
+                if insurance_inrc > max_insurance_inrc {
+                    return err!(ErrorCode::InsuranceDrawTooHigh);
+                }
+
+                if insurance_inrc > 0 {
+                    let burn_accounts = Burn {
+                        from: ctx.accounts.bidder_inrc_account.to_account_info(),
+                        mint: ctx.accounts.inrc_mint.to_account_info(),
+                        authority: ctx.accounts.bidder.to_account_info(),
+                    };
+
+                    token::burn(
+                        CpiContext::new(
+                            cpi_program.clone(),
+                            burn_accounts,
+                        ),
+                        insurance_inrc
+                    )?;
+
+                    let transfer_cpi_account = Transfer {
+                        from: ctx.accounts.insurance_vault.to_account_info(),
+                        to: ctx.accounts.bidder_usdc_account.to_account_info(),
+                        authority: ctx.accounts.treasury_authority.to_account_info(),
+                    };
+
+                    token::transfer(
+                        CpiContext::new_with_signer(
+                            cpi_program.clone(),
+                            transfer_cpi_account,
+                            signer_seeds,
+                        ),
+                        insurance_usdc
+                    )?;
+                    msg!("Insurance paid {} usdc to cover {} inrc of bad debt", insurance_usdc, insurance_inrc);
+                }
+            }
+
+            let uncovered = config.cover_from_surplus(bad_debt - insurance_inrc);
+            config.socialize_bad_debt(protocol_state.total_normalized_debt, uncovered)?;
+
+            auction.normalized_debt = 0;
+            auction.close(ctx.accounts.payer.to_account_info())?;
+            msg!("Auction finished with {} inrc of bad debt", bad_debt);
+        }
+
+        Ok(())
+    }
+
+    pub fn reset_auction(ctx: Context<ResetAuction>) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let config = & ctx.accounts.config;
+        let auction = &mut ctx.accounts.auction;
+
+        if clock.unix_timestamp.saturating_sub(auction.started_at) <= config.auction_duration {
+            return err!(ErrorCode::AuctionNotExpired);
+        }
+
+        auction.started_at = clock.unix_timestamp;
+        msg!("Auction reset for position {} of {}", auction.position_id, auction.owner);
+
+        Ok(())
+    }
+
+    // closes out a position whose collateral is worth less than its debt. the
+    // keeper buys all the collateral at the liquidation bonus and the debt
+    // that is left over is absorbed by the protocol. the keeper also has to
+    // buy insurance usdc against it, for at most `max_insurance_inrc`
+    pub fn settle_bad_debt(ctx: Context<SettleBadDebt>, _position_id: u64, max_insurance_inrc: u64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        let collateral_type = &mut ctx.accounts.collateral_type;
+        let user_collateral = &mut ctx.accounts.user_collateral;
+        let sort_key_before = SortedPositions::sort_key(user_collateral, collateral_type.decimals);
+        let clock = Clock::get()?;
+
+        if config.is_paused(PAUSE_LIQUIDATE) {
+            return err!(ErrorCode::LiquidationPaused);
+        }
+
+        config.accrue_stability_fee(protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let collateral_price = get_price(&ctx.accounts.price_feed.to_account_info(), &collateral_type.price_feed_id, recorded_keeper_feed(ctx.accounts.keeper_feed.as_deref(), &collateral_type.keeper_feed)?, clock.unix_timestamp, config)?.high();
+
+        let inrc_debt = config.debt_from_normalized(user_collateral.normalized_debt)?;
+        let collateral_value = collateral_value_in_inrc(user_collateral.collateral_amount, collateral_type.decimals, collateral_price)?;
+
+        if collateral_value >= inrc_debt as u128 {
+            return err!(ErrorCode::PositionNotUnderwater);
+        }
+
+        let inrc_to_burn = collateral_value
+            .checked_mul(100)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_div(100 + collateral_type.liquidation_bonus as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+        as u64;
+        let bad_debt = inrc_debt - inrc_to_burn;
+        let collateral_to_keeper = user_collateral.collateral_amount;
+
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let treasury_seeds = &[SEED_TREASURY_AUTHORITY,&[config.treasury_authority_bump]];
+        let signer_seeds = &[&treasury_seeds[..]];
+
+        if inrc_to_burn > 0 {
+            let burn_accounts = Burn {
+                from: ctx.accounts.keeper_inrc_account.to_account_info(),
+                mint: ctx.accounts.inrc_mint.to_account_info(),
+                authority: ctx.accounts.keeper.to_account_info(),
+            };
+
+            token::burn(
+                CpiContext::new(
+                    cpi_program.clone(),
+                    burn_accounts,
+                ),
+                inrc_to_burn
+            )?;
+        }
+
+        if collateral_to_keeper > 0 {
+            let transfer_cpi_account = Transfer {
+                from: ctx.accounts.collateral_vault.to_account_info(),
+                to: ctx.accounts.keeper_collateral_account.to_account_info(),
+                authority: ctx.accounts.treasury_authority.to_account_info(),
+            };
+
+            token::transfer(
+                CpiContext::new_with_signer(
+                    cpi_program.clone(),
+                    transfer_cpi_account,
+                    signer_seeds,
+                ),
+                collateral_to_keeper
+            )?;
+        }
+
+        collateral_type.total_normalized_debt = collateral_type.total_normalized_debt.checked_sub(user_collateral.normalized_debt).ok_or(ErrorCode::ArithmeticOverflow)?;
+        protocol_state.total_normalized_debt = protocol_state.total_normalized_debt.checked_sub(user_collateral.normalized_debt).ok_or(ErrorCode::ArithmeticOverflow)?;
+        protocol_state.debit_collateral(&user_collateral.collateral_mint, collateral_to_keeper)?;
+
+        user_collateral.collateral_amount = 0;
+        user_collateral.normalized_debt = 0;
+
+
+        // insurance usdc buys back as much of the bad debt as it can before
+        // surplus is used, and only what is left after that is socialized
+        let mut insurance_inrc = 0;
+
+        if bad_debt > 0 && ctx.accounts.insurance_vault.amount > 0 {
+            let usdc_price = get_price(&ctx.accounts.usdc_price_feed.to_account_info(), &config.usdc_price_feed_id, recorded_keeper_feed(ctx.accounts.usdc_keeper_feed.as_deref(), &config.usdc_keeper_feed)?, clock.unix_timestamp, config)?.price;
+            let insurance_usdc;
+            (insurance_inrc, insurance_usdc) = insurance_draw(
+                ctx.accounts.insurance_vault.amount,
+                config.usdc_decimals,
+                usdc_price,
+                bad_debt,
+            )?;
+
+            if insurance_inrc > max_insurance_inrc {
+                return err!(ErrorCode::InsuranceDrawTooHigh);
+            }
+
+            if insurance_inrc > 0 {
+                let burn_accounts = Burn {
+                    from: ctx.accounts.keeper_inrc_account.to_account_info(),
+                    mint: ctx.accounts.inrc_mint.to_account_info(),
+                    authority: ctx.accounts.keeper.to_account_info(),
+                };
+
+                token::burn(
+                    CpiContext::new(
+                        cpi_program.clone(),
+                        burn_accounts,
+                    ),
+                    insurance_inrc
+                )?;
+
+                let transfer_cpi_account = Transfer {
+                    from: ctx.accounts.insurance_vault.to_account_info(),
+                    to: ctx.accounts.keeper_usdc_account.to_account_info(),
+                    authority: ctx.accounts.treasury_authority.to_account_info(),
+                };
+
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        cpi_program.clone(),
+                        transfer_cpi_account,
+                        signer_seeds,
+                    ),
+                    insurance_usdc
+                )?;
+                msg!("Insurance paid {} usdc to cover {} inrc of bad debt", insurance_usdc, insurance_inrc);
+            }
+        }
+
+        let uncovered = config.cover_from_surplus(bad_debt - insurance_inrc);
+        config.socialize_bad_debt(protocol_state.total_normalized_debt, uncovered)?;
+        ctx.accounts.sorted_positions.load_mut()?.update(user_collateral, sort_key_before, collateral_type.decimals)?;
+        msg!("Settled position {} with {} inrc of bad debt", user_collateral.position_id, bad_debt);
+
+        Ok(())
+    }
+
+    pub fn initialize_stability_pool(ctx: Context<InitializeStabilityPool>) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let stability_pool = &mut ctx.accounts.stability_pool;
+
+        stability_pool.total_deposits = 0;
+        stability_pool.product = POOL_PRECISION;
+        stability_pool.epoch = 0;
+        stability_pool.scale = 0;
+        stability_pool.bump = ctx.bumps.stability_pool;
+        stability_pool.vault_bump = ctx.bumps.stability_pool_vault;
+        msg!("Stability pool initialized");
+
+        Ok(())
+    }
+
+    pub fn provide_to_stability_pool(ctx: Context<ProvideToStabilityPool>, amount_inrc: u64) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let config = & ctx.accounts.config;
+        let stability_pool = &mut ctx.accounts.stability_pool;
+        let stability_deposit = &mut ctx.accounts.stability_deposit;
+        let current_epoch = &mut ctx.accounts.current_epoch;
+
+        if config.is_paused(PAUSE_DEPOSIT) {
+            return err!(ErrorCode::DepositPaused);
+        }
+
+        if amount_inrc == 0 {
+            return err!(ErrorCode::InvalidAmount);
+        }
+
+        current_epoch.epoch = stability_pool.epoch;
+        current_epoch.scale = stability_pool.scale;
+        current_epoch.bump = ctx.bumps.current_epoch;
+
+        if stability_deposit.owner == Pubkey::default() {
+            stability_deposit.owner = ctx.accounts.depositor.key();
+            stability_deposit.bump = ctx.bumps.stability_deposit;
+        }
+
+        settle_stability_deposit(stability_pool, stability_deposit, &ctx.accounts.depositor_epoch, &ctx.accounts.depositor_next_scale, current_epoch)?;
+
+        let cpi_account = Transfer {
+            from: ctx.accounts.depositor_inrc_account.to_account_info(),
+            to: ctx.accounts.stability_pool_vault.to_account_info(),
+            authority: ctx.accounts.depositor.to_account_info(),
+        };
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_account,
+            ),
+            amount_inrc
+        )?;
+
+        stability_deposit.initial_amount = stability_deposit.initial_amount.checked_add(amount_inrc).ok_or(ErrorCode::ArithmeticOverflow)?;
+        stability_pool.total_deposits = stability_pool.total_deposits.checked_add(amount_inrc).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    pub fn withdraw_from_stability_pool(ctx: Context<WithdrawFromStabilityPool>, amount_inrc: u64) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let config = & ctx.accounts.config;
+        let stability_pool = &mut ctx.accounts.stability_pool;
+        let stability_deposit = &mut ctx.accounts.stability_deposit;
+        let current_epoch = &mut ctx.accounts.current_epoch;
+
+        if config.is_paused(PAUSE_WITHDRAW) {
+            return err!(ErrorCode::WithdrawPaused);
+        }
+
+        if amount_inrc == 0 {
+            return err!(ErrorCode::InvalidAmount);
+        }
+
+        current_epoch.epoch = stability_pool.epoch;
+        current_epoch.scale = stability_pool.scale;
+        current_epoch.bump = ctx.bumps.current_epoch;
+
+        settle_stability_deposit(stability_pool, stability_deposit, &ctx.accounts.depositor_epoch, &ctx.accounts.depositor_next_scale, current_epoch)?;
+
+        if amount_inrc > stability_deposit.initial_amount {
+            return err!(ErrorCode::InsufficientFunds);
+        }
+
+        let transfer_cpi_account = Transfer {
+            from: ctx.accounts.stability_pool_vault.to_account_info(),
+            to: ctx.accounts.depositor_inrc_account.to_account_info(),
+            authority: ctx.accounts.treasury_authority.to_account_info(),
+        };
+
+        let treasury_seeds = &[SEED_TREASURY_AUTHORITY,&[config.treasury_authority_bump]];
+        let signer_seeds = &[&treasury_seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_cpi_account,
+                signer_seeds,
+            ),
+            amount_inrc
+        )?;
+
+        stability_deposit.initial_amount -= amount_inrc;
+        stability_pool.total_deposits = stability_pool.total_deposits.checked_sub(amount_inrc).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    pub fn claim_stability_pool_gain(ctx: Context<ClaimStabilityPoolGain>) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let config = & ctx.accounts.config;
+        let stability_pool = & ctx.accounts.stability_pool;
+        let stability_deposit = &mut ctx.accounts.stability_deposit;
+        let current_epoch = &mut ctx.accounts.current_epoch;
+
+        if config.is_paused(PAUSE_WITHDRAW) {
+            return err!(ErrorCode::WithdrawPaused);
+        }
+
+        current_epoch.epoch = stability_pool.epoch;
+        current_epoch.scale = stability_pool.scale;
+        current_epoch.bump = ctx.bumps.current_epoch;
+
+        settle_stability_deposit(stability_pool, stability_deposit, &ctx.accounts.depositor_epoch, &ctx.accounts.depositor_next_scale, current_epoch)?;
+
+        let collateral_gain = stability_deposit.take_gain(&ctx.accounts.collateral_mint.key());
+
+        if collateral_gain == 0 {
+            return err!(ErrorCode::InvalidAmount);
+        }
+
+        let transfer_cpi_account = Transfer {
+            from: ctx.accounts.collateral_vault.to_account_info(),
+            to: ctx.accounts.depositor_collateral_account.to_account_info(),
+            authority: ctx.accounts.treasury_authority.to_account_info(),
+        };
+
+        let treasury_seeds = &[SEED_TREASURY_AUTHORITY,&[config.treasury_authority_bump]];
+        let signer_seeds = &[&treasury_seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_cpi_account,
+                signer_seeds,
+            ),
+            collateral_gain
+        )?;
+        msg!("Claimed {} collateral from the stability pool", collateral_gain);
+
+        Ok(())
+    }
+
+    // the pool repays the debt with pooled inrc and its depositors get the
+    // seized collateral, bonus included. the collateral stays in the vault
+    // until they claim it
+    pub fn liquidate_with_stability_pool(ctx: Context<LiquidateWithStabilityPool>, _position_id: u64, amount_inrc_to_burn: u64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        let collateral_type = &mut ctx.accounts.collateral_type;
+        let user_collateral = &mut ctx.accounts.user_collateral;
+        let sort_key_before = SortedPositions::sort_key(user_collateral, collateral_type.decimals);
+        let stability_pool = &mut ctx.accounts.stability_pool;
+        let clock = Clock::get()?;
+
+        if config.is_paused(PAUSE_LIQUIDATE) {
+            return err!(ErrorCode::LiquidationPaused);
+        }
+
+        if config.liquidation_mode != LiquidationMode::FixedBonus {
+            return err!(ErrorCode::LiquidationModeMismatch);
+        }
+
+        if amount_inrc_to_burn > stability_pool.total_deposits {
+            return err!(ErrorCode::InsufficientStabilityPoolDeposits);
+        }
+
+        config.accrue_stability_fee(protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let oracle_price = get_price(&ctx.accounts.price_feed.to_account_info(), &collateral_type.price_feed_id, recorded_keeper_feed(ctx.accounts.keeper_feed.as_deref(), &collateral_type.keeper_feed)?, clock.unix_timestamp, config)?;
+        let collateral_price = oracle_price.price;
+        let health_price = get_price_with_mode(&ctx.accounts.price_feed.to_account_info(), &collateral_type.price_feed_id, recorded_keeper_feed(ctx.accounts.keeper_feed.as_deref(), &collateral_type.keeper_feed)?, ctx.accounts.price_history.as_deref().map(|price_history| &**price_history), clock.unix_timestamp, config, config.liquidation_price_mode)?.high();
+
+        let inrc_debt = config.debt_from_normalized(user_collateral.normalized_debt)?;
+        let health_factor = health_factor(
+            collateral_value_in_inrc(user_collateral.collateral_amount, collateral_type.decimals, health_price)?,
+            inrc_debt,
+        )?;
+
+        if health_factor >= collateral_type.liquidation_threshold as u128 {
+            return err!(ErrorCode::AboveMinHealthFactor);
+        }
+
+        if amount_inrc_to_burn > config.max_liquidation_amount(inrc_debt, health_factor)? {
+            return err!(ErrorCode::LiquidationAmountTooHigh);
+        }
+
+        let normalized_repayment = config.normalized_repayment(user_collateral.normalized_debt, amount_inrc_to_burn)?;
+        config.check_position_debt(user_collateral.normalized_debt.checked_sub(normalized_repayment).ok_or(ErrorCode::ArithmeticOverflow)?)?;
+
+        let collateral_to_pool = (inrc_value_in_collateral(amount_inrc_to_burn, collateral_type.decimals, collateral_price)? as u128)
+            .checked_mul(100 + collateral_type.liquidation_bonus as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_div(100)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+        as u64;
+
+        if collateral_to_pool > user_collateral.collateral_amount {
+            return err!(ErrorCode::InsufficientCollateralForLiquidation);
+        }
+
+        let burn_accounts = Burn {
+            mint: ctx.accounts.inrc_mint.to_account_info(),
+            from: ctx.accounts.stability_pool_vault.to_account_info(),
+            authority: ctx.accounts.treasury_authority.to_account_info(),
+        };
+
+        let treasury_seeds = &[SEED_TREASURY_AUTHORITY,&[config.treasury_authority_bump]];
+        let signer_seeds = &[&treasury_seeds[..]];
+
+        token::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                burn_accounts,
+                signer_seeds,
+            ),
+            amount_inrc_to_burn
+        )?;
+
+        let current_epoch = &mut ctx.accounts.current_epoch;
+        current_epoch.epoch = stability_pool.epoch;
+        current_epoch.scale = stability_pool.scale;
+        current_epoch.bump = ctx.bumps.current_epoch;
+        stability_pool.offset(current_epoch, amount_inrc_to_burn, user_collateral.collateral_mint, collateral_to_pool)?;
+
+        user_collateral.collateral_amount = user_collateral.collateral_amount
+        .checked_sub(collateral_to_pool)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        protocol_state.debit_collateral(&user_collateral.collateral_mint, collateral_to_pool)?;
+
+        user_collateral.normalized_debt = user_collateral.normalized_debt
+        .checked_sub(normalized_repayment)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        collateral_type.total_normalized_debt = collateral_type.total_normalized_debt
+        .checked_sub(normalized_repayment)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        protocol_state.total_normalized_debt = protocol_state.total_normalized_debt
+        .checked_sub(normalized_repayment)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+        ctx.accounts.sorted_positions.load_mut()?.update(user_collateral, sort_key_before, collateral_type.decimals)?;
+        msg!("Stability pool absorbed {} inrc of debt for {} collateral", amount_inrc_to_burn, collateral_to_pool);
+
+        Ok(())
+    }
+
+    // burns up to `amount_inrc` against the positions with the lowest health
+    // factor and pays out their collateral at the oracle price. the
+    // redemption fee is paid in inrc on top
+    pub fn redeem(ctx: Context<Redeem>, amount_inrc: u64, min_amount_collateral: u64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        let collateral_type = &mut ctx.accounts.collateral_type;
+        let clock = Clock::get()?;
+
+        if config.is_paused(PAUSE_REPAY) {
+            return err!(ErrorCode::RepayPaused);
+        }
+
+        if amount_inrc == 0 {
+            return err!(ErrorCode::InvalidAmount);
+        }
+
+        config.accrue_stability_fee(protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        // positions are judged and paid out at the same mid price
+        let collateral_price = get_price(&ctx.accounts.price_feed.to_account_info(), &collateral_type.price_feed_id, recorded_keeper_feed(ctx.accounts.keeper_feed.as_deref(), &collateral_type.keeper_feed)?, clock.unix_timestamp, config)?.price;
+
+        let mut sorted_positions = ctx.accounts.sorted_positions.load_mut()?;
+        let mut inrc_left = amount_inrc;
+        let mut inrc_redeemed: u64 = 0;
+        let mut collateral_redeemed: u64 = 0;
+        let mut normalized_redeemed: u64 = 0;
+        // liquidatable positions are left for liquidators and skipped over
+        let mut skipped: usize = 0;
+
+        for account_info in ctx.remaining_accounts.iter() {
+            let next = match sorted_positions.get(skipped) {
+                Some(next) => next,
+                None => break,
+            };
+
+            if inrc_left == 0 {
+                break;
+            }
+
+            let mut position = load_user_collateral(account_info)?;
+
+            if position.depositor != next.owner || position.position_id != next.position_id || position.collateral_mint != collateral_type.mint {
+                return err!(ErrorCode::InvalidRedemptionOrder);
+            }
+
+            let inrc_debt = config.debt_from_normalized(position.normalized_debt)?;
+            let health_factor = health_factor(
+                collateral_value_in_inrc(position.collateral_amount, collateral_type.decimals, collateral_price)?,
+                inrc_debt,
+            )?;
+
+            if health_factor < collateral_type.liquidation_threshold as u128 {
+                skipped += 1;
+                continue;
+            }
+
+            // a partial redemption stops short of leaving dust debt behind
+            let mut inrc_to_redeem = inrc_left.min(inrc_debt);
+            if inrc_to_redeem < inrc_debt && inrc_debt - inrc_to_redeem < config.min_position_debt {
+                inrc_to_redeem = inrc_debt.saturating_sub(config.min_position_debt);
+            }
+
+            if inrc_to_redeem == 0 {
+                break;
+            }
+
+            let collateral_to_redeem = inrc_value_in_collateral(inrc_to_redeem, collateral_type.decimals, collateral_price)?;
+            let normalized_repayment = config.normalized_repayment(position.normalized_debt, inrc_to_redeem)?;
+            let sort_key_before = SortedPositions::sort_key(&position, collateral_type.decimals);
+
+            position.collateral_amount = position.collateral_amount.checked_sub(collateral_to_redeem).ok_or(ErrorCode::ArithmeticOverflow)?;
+            position.normalized_debt = position.normalized_debt.checked_sub(normalized_repayment).ok_or(ErrorCode::ArithmeticOverflow)?;
+            sorted_positions.update(&position, sort_key_before, collateral_type.decimals)?;
+            position.try_serialize(&mut &mut account_info.try_borrow_mut_data()?[..])?;
+
+            inrc_left -= inrc_to_redeem;
+            inrc_redeemed = inrc_redeemed.checked_add(inrc_to_redeem).ok_or(ErrorCode::ArithmeticOverflow)?;
+            collateral_redeemed = collateral_redeemed.checked_add(collateral_to_redeem).ok_or(ErrorCode::ArithmeticOverflow)?;
+            normalized_redeemed = normalized_redeemed.checked_add(normalized_repayment).ok_or(ErrorCode::ArithmeticOverflow)?;
+        }
+        drop(sorted_positions);
+
+        if collateral_redeemed == 0 || collateral_redeemed < min_amount_collateral {
+            return err!(ErrorCode::SlippageExceeded);
+        }
+
+        let redemption_fee = fee_amount(inrc_redeemed, config.redemption_fee_bps)?;
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+
+        let burn_accounts = Burn {
+            mint: ctx.accounts.inrc_mint.to_account_info(),
+            from: ctx.accounts.redeemer_inrc_account.to_account_info(),
+            authority: ctx.accounts.redeemer.to_account_info(),
+        };
+
+        token::burn(
+            CpiContext::new(
+                cpi_program.clone(),
+                burn_accounts,
+            ),
+            inrc_redeemed
+        )?;
+
+        if redemption_fee > 0 {
+            let fee_transfer_account = Transfer {
+                from: ctx.accounts.redeemer_inrc_account.to_account_info(),
+                to: ctx.accounts.fee_vault.to_account_info(),
+                authority: ctx.accounts.redeemer.to_account_info(),
+            };
+
+            token::transfer(
+                CpiContext::new(
+                    cpi_program.clone(),
+                    fee_transfer_account,
+                ),
+                redemption_fee
+            )?;
+        }
+
+        config.reserve_insurance_fee(redemption_fee)?;
+
+        let transfer_cpi_account = Transfer {
+            from: ctx.accounts.collateral_vault.to_account_info(),
+            to: ctx.accounts.redeemer_collateral_account.to_account_info(),
+            authority: ctx.accounts.treasury_authority.to_account_info(),
+        };
+
+        let treasury_seeds = &[SEED_TREASURY_AUTHORITY,&[config.treasury_authority_bump]];
+        let signer_seeds = &[&treasury_seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                cpi_program,
+                transfer_cpi_account,
+                signer_seeds,
+            ),
+            collateral_redeemed
+        )?;
+
+        collateral_type.total_normalized_debt = collateral_type.total_normalized_debt.checked_sub(normalized_redeemed).ok_or(ErrorCode::ArithmeticOverflow)?;
+        protocol_state.total_normalized_debt = protocol_state.total_normalized_debt.checked_sub(normalized_redeemed).ok_or(ErrorCode::ArithmeticOverflow)?;
+        protocol_state.debit_collateral(&collateral_type.mint, collateral_redeemed)?;
+        msg!("Redeemed {} inrc for {} collateral", inrc_redeemed, collateral_redeemed);
+
+        Ok(())
+    }
+
+    pub fn initialize_psm(ctx: Context<InitializePsm>) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        ctx.accounts.psm.minted_inrc = 0;
+        ctx.accounts.psm.bump = ctx.bumps.psm;
+        ctx.accounts.psm.reserve_bump = ctx.bumps.psm_reserve;
+        msg!("PSM initialized");
+
+        Ok(())
+    }
+
+    pub fn initialize_keeper_feed(ctx: Context<InitializeKeeperFeed>, keeper: Pubkey) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let keeper_feed = &mut ctx.accounts.keeper_feed;
+        keeper_feed.mint = ctx.accounts.mint.key();
+        keeper_feed.keeper = keeper;
+        keeper_feed.bump = ctx.bumps.keeper_feed;
+        msg!("Keeper feed initialized for {}", keeper_feed.mint);
+
+        Ok(())
+    }
+
+    pub fn set_feed_keeper(ctx: Context<SetFeedKeeper>, keeper: Pubkey) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        ctx.accounts.keeper_feed.keeper = keeper;
+        msg!("Keeper set to {}", keeper);
+
+        Ok(())
+    }
+
+    // the keeper posts a pyth style price, it is timestamped on arrival
+    pub fn update_keeper_feed(ctx: Context<UpdateKeeperFeed>, price: i64, conf: u64, expo: i32) -> Result<()> {
+        if price <= 0 || conf > price as u64 {
+            return err!(ErrorCode::InvalidPrice);
+        }
+
+        if !(MIN_KEEPER_PRICE_EXPO..=MAX_KEEPER_PRICE_EXPO).contains(&expo) {
+            return err!(ErrorCode::InvalidPrice);
+        }
+
+        let clock = Clock::get()?;
+        let keeper_feed = &mut ctx.accounts.keeper_feed;
+        keeper_feed.price = price;
+        keeper_feed.conf = conf;
+        keeper_feed.expo = expo;
+        keeper_feed.publish_time = clock.unix_timestamp;
+
+        Ok(())
+    }
+
+    pub fn initialize_price_history(ctx: Context<InitializePriceHistory>) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let price_history = &mut ctx.accounts.price_history;
+        price_history.mint = ctx.accounts.collateral_type.mint;
+        price_history.bump = ctx.bumps.price_history;
+        msg!("Price history initialized for {}", price_history.mint);
+
+        Ok(())
+    }
+
+    // anyone can record the current spot price, at most once per
+    // PRICE_OBSERVATION_INTERVAL
+    pub fn record_price(ctx: Context<RecordPrice>) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let oracle_price = get_price(&ctx.accounts.price_feed.to_account_info(), &ctx.accounts.collateral_type.price_feed_id, recorded_keeper_feed(ctx.accounts.keeper_feed.as_deref(), &ctx.accounts.collateral_type.keeper_feed)?, clock.unix_timestamp, &ctx.accounts.config)?;
+        // the twap is only built from primary prices
+        if oracle_price.source == PriceSource::Keeper {
+            return err!(ErrorCode::KeeperPriceNotAllowed);
+        }
+        ctx.accounts.price_history.record(oracle_price.price, clock.unix_timestamp)?;
+
+        Ok(())
+    }
+
+    // usdc in, inrc out at the oracle rate. the fee is minted to the fee vault
+    pub fn psm_swap_in(ctx: Context<PsmSwap>, amount_usdc: u64, min_amount_inrc: u64) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let config = &mut ctx.accounts.config;
+        let psm = &mut ctx.accounts.psm;
+
+        if config.is_paused(PAUSE_MINT) {
+            return err!(ErrorCode::MintPaused);
+        }
+
+        if amount_usdc == 0 {
+            return err!(ErrorCode::InvalidAmount);
+        }
+
+        // usdc backs newly minted inrc here, so it is valued like collateral
+        let usdc_price = get_price(&ctx.accounts.usdc_price_feed.to_account_info(), &config.usdc_price_feed_id, recorded_keeper_feed(ctx.accounts.usdc_keeper_feed.as_deref(), &config.usdc_keeper_feed)?, clock.unix_timestamp, config)?.borrow_price()?;
+        let amount_inrc = u64::try_from(collateral_value_in_inrc(amount_usdc, config.usdc_decimals, usdc_price)?)
+            .map_err(|_| ErrorCode::ArithmeticOverflow)?;
+        let swap_fee = fee_amount(amount_inrc, config.psm_fee_in_bps)?;
+        let amount_out = amount_inrc - swap_fee;
+
+        if amount_out == 0 || amount_out < min_amount_inrc {
+            return err!(ErrorCode::SlippageExceeded);
+        }
+
+        let minted_inrc_after = psm.minted_inrc.checked_add(amount_inrc).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        if minted_inrc_after > config.psm_capacity {
+            return err!(ErrorCode::PsmCapacityExceeded);
+        }
+
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+
+        let transfer_cpi_account = Transfer {
+            from: ctx.accounts.user_usdc_account.to_account_info(),
+            to: ctx.accounts.psm_reserve.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+
+        token::transfer(
+            CpiContext::new(
+                cpi_program.clone(),
+                transfer_cpi_account,
+            ),
+            amount_usdc
+        )?;
+
+        let treasury_seeds = &[SEED_TREASURY_AUTHORITY,&[config.treasury_authority_bump]];
+        let signer_seeds = &[&treasury_seeds[..]];
+
+        let mint_to_account = MintTo {
+            mint: ctx.accounts.inrc_mint.to_account_info(),
+            to: ctx.accounts.user_inrc_account.to_account_info(),
+            authority: ctx.accounts.treasury_authority.to_account_info(),
+        };
+
+        mint_to(
+            CpiContext::new_with_signer(
+                cpi_program.clone(),
+                mint_to_account,
+                signer_seeds,
+            ),
+            amount_out
+        )?;
+
+        if swap_fee > 0 {
+            let fee_mint_to_account = MintTo {
+                mint: ctx.accounts.inrc_mint.to_account_info(),
+                to: ctx.accounts.fee_vault.to_account_info(),
+                authority: ctx.accounts.treasury_authority.to_account_info(),
+            };
+
+            mint_to(
+                CpiContext::new_with_signer(
+                    cpi_program.clone(),
+                    fee_mint_to_account,
+                    signer_seeds,
+                ),
+                swap_fee
+            )?;
+        }
+
+        config.reserve_insurance_fee(swap_fee)?;
+
+        psm.minted_inrc = minted_inrc_after;
+        msg!("PSM swapped {} usdc for {} inrc", amount_usdc, amount_out);
+
+        Ok(())
+    }
+
+    // inrc in, usdc out of the psm reserve at the oracle rate. the fee is
+    // taken in inrc before the rest is burned
+    pub fn psm_swap_out(ctx: Context<PsmSwap>, amount_inrc: u64, min_amount_usdc: u64) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let config = &mut ctx.accounts.config;
+        let psm = &mut ctx.accounts.psm;
+
+        if config.is_paused(PAUSE_REPAY) {
+            return err!(ErrorCode::RepayPaused);
+        }
+
+        if amount_inrc == 0 {
+            return err!(ErrorCode::InvalidAmount);
+        }
+
+        let swap_fee = fee_amount(amount_inrc, config.psm_fee_out_bps)?;
+        let amount_to_burn = amount_inrc - swap_fee;
+
+        let usdc_price = get_price(&ctx.accounts.usdc_price_feed.to_account_info(), &config.usdc_price_feed_id, recorded_keeper_feed(ctx.accounts.usdc_keeper_feed.as_deref(), &config.usdc_keeper_feed)?, clock.unix_timestamp, config)?.price;
+        let amount_out = inrc_value_in_collateral(amount_to_burn, config.usdc_decimals, usdc_price)?;
+
+        if amount_out == 0 || amount_out < min_amount_usdc {
+            return err!(ErrorCode::SlippageExceeded);
+        }
+
+        if amount_out > ctx.accounts.psm_reserve.amount {
+            return err!(ErrorCode::InsufficientPsmReserve);
+        }
+
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+
+        if swap_fee > 0 {
+            let fee_cpi_account = Transfer {
+                from: ctx.accounts.user_inrc_account.to_account_info(),
+                to: ctx.accounts.fee_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            };
+
+            token::transfer(
+                CpiContext::new(
+                    cpi_program.clone(),
+                    fee_cpi_account,
+                ),
+                swap_fee
+            )?;
+        }
+
+        config.reserve_insurance_fee(swap_fee)?;
+
+        let burn_accounts = Burn {
+            mint: ctx.accounts.inrc_mint.to_account_info(),
+            from: ctx.accounts.user_inrc_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+
+        token::burn(
+            CpiContext::new(
+                cpi_program.clone(),
+                burn_accounts,
+            ),
+            amount_to_burn
+        )?;
+
+        let transfer_cpi_account = Transfer {
+            from: ctx.accounts.psm_reserve.to_account_info(),
+            to: ctx.accounts.user_usdc_account.to_account_info(),
+            authority: ctx.accounts.treasury_authority.to_account_info(),
+        };
+
+        let treasury_seeds = &[SEED_TREASURY_AUTHORITY,&[config.treasury_authority_bump]];
+        let signer_seeds = &[&treasury_seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                cpi_program.clone(),
+                transfer_cpi_account,
+                signer_seeds,
+            ),
+            amount_out
+        )?;
+
+        // inrc minted elsewhere can be swapped out too, so this only ever
+        // frees capacity down to zero
+        psm.minted_inrc = psm.minted_inrc.saturating_sub(amount_to_burn);
+        msg!("PSM swapped {} inrc for {} usdc", amount_inrc, amount_out);
+
+        Ok(())
+    }
+
+    pub fn deposit_portfolio_collateral(ctx: Context<DepositPortfolioCollateral>, amount_collateral: u64) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let config = & ctx.accounts.config;
+        let portfolio = &mut ctx.accounts.portfolio;
+        let collateral_mint = ctx.accounts.collateral_mint.key();
+
+        if config.is_paused(PAUSE_DEPOSIT) {
+            return err!(ErrorCode::DepositPaused);
+        }
+
+        if amount_collateral == 0 {
+            return err!(ErrorCode::InvalidAmount);
+        }
+
+        if portfolio.owner == Pubkey::default() {
+            portfolio.owner = ctx.accounts.signer.key();
+            portfolio.bump = ctx.bumps.portfolio;
+            msg!("Portfolio created for {}", portfolio.owner);
+        }
+
+        if ctx.accounts.user_collateral_account.amount < amount_collateral {
+            return err!(ErrorCode::InsufficientFunds);
+        }
+
+        match portfolio.balances.iter_mut().find(|balance| balance.collateral_mint == collateral_mint) {
+            Some(balance) => {
+                balance.amount = balance.amount.checked_add(amount_collateral).ok_or(ErrorCode::ArithmeticOverflow)?;
+            }
+            None => {
+                if portfolio.balances.len() >= MAX_PORTFOLIO_ASSETS as usize {
+                    return err!(ErrorCode::PortfolioFull);
+                }
+                portfolio.balances.push(PortfolioBalance {
+                    collateral_mint,
+                    amount: amount_collateral,
+                    normalized_debt: 0,
+                });
+            }
+        }
+
+        ctx.accounts.protocol_state.credit_collateral(&collateral_mint, amount_collateral)?;
+
+        let cpi_account = Transfer {
+            from: ctx.accounts.user_collateral_account.to_account_info(),
+            to: ctx.accounts.collateral_vault.to_account_info(),
+            authority: ctx.accounts.signer.to_account_info(),
+        };
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_account,
+            ),
+            amount_collateral
+        )?;
+
+        Ok(())
+    }
+
+    // remaining accounts: CollateralType, price feed, keeper feed and price
+    // history for every balance left after the withdrawal, in portfolio order
+    pub fn withdraw_portfolio_collateral(ctx: Context<WithdrawPortfolioCollateral>, amount_collateral: u64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let portfolio = &mut ctx.accounts.portfolio;
+        let collateral_mint = ctx.accounts.collateral_mint.key();
+        let clock = Clock::get()?;
+
+        if config.is_paused(PAUSE_WITHDRAW) {
+            return err!(ErrorCode::WithdrawPaused);
+        }
+
+        if amount_collateral == 0 {
+            return err!(ErrorCode::InvalidAmount);
+        }
+
+        config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+        debit_portfolio_balance(portfolio, &collateral_mint, amount_collateral)?;
+        portfolio.prune_balances();
+        ctx.accounts.protocol_state.debit_collateral(&collateral_mint, amount_collateral)?;
+
+        if portfolio.normalized_debt > 0 {
+            let weighted_value = portfolio_weighted_value(&portfolio.balances, ctx.remaining_accounts, clock.unix_timestamp, config, PriceMode::Spot, OraclePrice::borrow_price, |collateral_type| collateral_type.min_health_factor)?;
+
+            if health_factor(weighted_value, config.debt_from_normalized(portfolio.normalized_debt)?)? < 100 {
+                return err!(ErrorCode::BelowMinHealthFactor);
+            }
+
+            // the debt moves with the collateral, onto types that have room for it
+            charge_portfolio_debt(portfolio, ctx.remaining_accounts, clock.unix_timestamp, config, true)?;
+        }
+
+        let transfer_cpi_account = Transfer {
+            from: ctx.accounts.collateral_vault.to_account_info(),
+            to: ctx.accounts.user_collateral_account.to_account_info(),
+            authority: ctx.accounts.treasury_authority.to_account_info(),
+        };
+
+        let treasury_seeds = &[SEED_TREASURY_AUTHORITY,&[config.treasury_authority_bump]];
+        let signer_seeds = &[&treasury_seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_cpi_account,
+                signer_seeds,
+            ),
+            amount_collateral
+        )?;
+
+        Ok(())
+    }
+
+    // remaining accounts: CollateralType, price feed, keeper feed and price
+    // history for every portfolio balance, in order. the CollateralTypes are
+    // writable, the new debt is charged to them
+    pub fn mint_portfolio_inrc(ctx: Context<MintPortfolioInrc>, amount_inrc: u64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        let portfolio = &mut ctx.accounts.portfolio;
+        let clock = Clock::get()?;
+
+        if config.is_paused(PAUSE_MINT) {
+            return err!(ErrorCode::MintPaused);
+        }
+
+        if amount_inrc == 0 {
+            return err!(ErrorCode::InvalidAmount);
+        }
+
+        config.accrue_stability_fee(protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let mint_fee = fee_amount(amount_inrc, config.mint_fee_bps)?;
+        let normalized_amount = config.normalize_debt(amount_inrc.checked_add(mint_fee).ok_or(ErrorCode::ArithmeticOverflow)?)?;
+        let normalized_debt_after = portfolio.normalized_debt.checked_add(normalized_amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+        config.check_position_debt(normalized_debt_after)?;
+
+        let protocol_normalized_debt_after_mint = protocol_state.total_normalized_debt.checked_add(normalized_amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        if config.debt_from_normalized(protocol_normalized_debt_after_mint)? > config.debt_ceiling {
+            return err!(ErrorCode::GlobalDebtCeilingExceeded);
+        }
+
+        let weighted_value = portfolio_weighted_value(&portfolio.balances, ctx.remaining_accounts, clock.unix_timestamp, config, PriceMode::Spot, OraclePrice::borrow_price, |collateral_type| collateral_type.min_health_factor)?;
+
+        if health_factor(weighted_value, config.debt_from_normalized(normalized_debt_after)?)? < 100 {
+            return err!(ErrorCode::BelowMinHealthFactor);
+        }
+
+        let mint_to_account = MintTo {
+            mint: ctx.accounts.inrc_mint.to_account_info(),
+            to: ctx.accounts.user_inrc_account.to_account_info(),
+            authority: ctx.accounts.treasury_authority.to_account_info(),
+        };
+
+        let treasury_seeds = &[SEED_TREASURY_AUTHORITY,&[config.treasury_authority_bump]];
+        let signer_seeds = &[&treasury_seeds[..]];
+
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                mint_to_account,
+                signer_seeds,
+            ),
+            amount_inrc
+        )?;
 
-        if inrc_to_mint > 0 {
-            let mint_to_account = MintTo {
+        if mint_fee > 0 {
+            let fee_mint_to_account = MintTo {
                 mint: ctx.accounts.inrc_mint.to_account_info(),
-                to: ctx.accounts.user_inrc_account.to_account_info(),
+                to: ctx.accounts.fee_vault.to_account_info(),
                 authority: ctx.accounts.treasury_authority.to_account_info(),
             };
-            
-            let trasury_authority_seeds = &[SEED_TREASURY_AUTHORITY,&[config.treasury_authority_bump]];
-
-            let signer_seeds = &[&trasury_authority_seeds[..]];
 
             mint_to(
                 CpiContext::new_with_signer(
-                    cpi_program,
-                    mint_to_account,
+                    ctx.accounts.token_program.to_account_info(),
+                    fee_mint_to_account,
                     signer_seeds,
                 ),
-                inrc_to_mint
+                mint_fee
             )?;
-
         }
-        user_collateral.usdc_deposit = total_usdc_after_deposit;
-        user_collateral.inrc_minted = user_collateral.inrc_minted.checked_add(inrc_to_mint).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        config.reserve_insurance_fee(mint_fee)?;
+
+        portfolio.normalized_debt = normalized_debt_after;
+        protocol_state.total_normalized_debt = protocol_normalized_debt_after_mint;
+        charge_portfolio_debt(portfolio, ctx.remaining_accounts, clock.unix_timestamp, config, true)?;
 
         Ok(())
     }
 
-     pub fn burn_inrc_and_withdraw_usdc(ctx: Context<BurnInrcAndWithdrawUsdc>, amount_inrc: u64) -> Result<()> {
-        let config = & ctx.accounts.config;
-        let user_collateral = &mut ctx.accounts.user_collateral;
+    // remaining accounts: the same four accounts per balance as
+    // mint_portfolio_inrc, the repaid debt comes off the CollateralTypes
+    pub fn repay_portfolio_inrc(ctx: Context<RepayPortfolioInrc>, amount_inrc: u64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        let portfolio = &mut ctx.accounts.portfolio;
         let clock = Clock::get()?;
-        
+
+        if config.is_paused(PAUSE_REPAY) {
+            return err!(ErrorCode::RepayPaused);
+        }
+
         if amount_inrc == 0 {
             return err!(ErrorCode::InvalidAmount);
         }
-        
-        if amount_inrc > user_collateral.inrc_minted {
-            return err!(ErrorCode::LiquidationAmountTooHigh);
-        }
-
-        let usdc_inr_price = get_pyth_price(&ctx.accounts.usdc_inr_price_feed.to_account_info(), clock.unix_timestamp, MAX_AGE, TARGET_PRICE_DECIMALS)?;
-
-        let remaining_inrc = user_collateral.inrc_minted.checked_sub(amount_inrc).ok_or(ErrorCode::ArithmeticOverflow)?;
-
-        let current_usdc_value_in_inr = (user_collateral.usdc_deposit as u128)
-            .checked_mul(usdc_inr_price)
-            .ok_or(ErrorCode::ArithmeticOverflow)?;
-
-        let health_factor_after_withdrawal = if remaining_inrc > 0 {
-            current_usdc_value_in_inr
-                .checked_mul(100)
-                .ok_or(ErrorCode::ArithmeticOverflow)?
-                .checked_div(remaining_inrc as u128)
-                .ok_or(ErrorCode::ArithmeticOverflow)?
-                .checked_mul(10u128.pow((TARGET_PRICE_DECIMALS - MINT_DECIMAL as i32) as u32))
-                .ok_or(ErrorCode::ArithmeticOverflow)?
-        } else {
-            u128::MAX 
-        };
-
-        //verifying if its above the health factor
-        //in which we minted the inrc.. should be 120%
-        if health_factor_after_withdrawal < config.min_health_factor as u128 {
-            return err!(ErrorCode::BelowMinHealthFactor);
-        };
 
-        let usdc_to_withdraw = (amount_inrc as u128)
-            .checked_mul(10u128.pow((TARGET_PRICE_DECIMALS - MINT_DECIMAL as i32) as u32))
-            .ok_or(ErrorCode::ArithmeticOverflow)?
-            .checked_div(usdc_inr_price)
-            .ok_or(ErrorCode::ArithmeticOverflow)?
-            as u64;
+        config.accrue_stability_fee(protocol_state.total_normalized_debt, clock.unix_timestamp)?;
 
+        let normalized_repayment = config.normalized_repayment(portfolio.normalized_debt, amount_inrc)?;
+        config.check_position_debt(portfolio.normalized_debt.checked_sub(normalized_repayment).ok_or(ErrorCode::ArithmeticOverflow)?)?;
+        let redeem_fee = fee_amount(amount_inrc, config.redeem_fee_bps)?;
 
         let burn_accounts = Burn {
             mint: ctx.accounts.inrc_mint.to_account_info(),
@@ -187,92 +2279,98 @@ pub mod contract_inrc {
             authority: ctx.accounts.signer.to_account_info(),
         };
 
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-
         token::burn(
             CpiContext::new(
-                cpi_program.clone(),
+                ctx.accounts.token_program.to_account_info(),
                 burn_accounts,
             ),
             amount_inrc
         )?;
 
-        let transfer_cpi_account = Transfer {
-            from: ctx.accounts.usdc_treasury_account.to_account_info(),
-            to: ctx.accounts.user_usdc_account.to_account_info(),
-            authority: ctx.accounts.treasury_authority.to_account_info(),
-        };
+        if redeem_fee > 0 {
+            let fee_transfer_account = Transfer {
+                from: ctx.accounts.user_inrc_account.to_account_info(),
+                to: ctx.accounts.fee_vault.to_account_info(),
+                authority: ctx.accounts.signer.to_account_info(),
+            };
 
-        let treasury_seeds = &[SEED_TREASURY_AUTHORITY,&[config.treasury_authority_bump]];
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    fee_transfer_account,
+                ),
+                redeem_fee
+            )?;
+        }
 
-        let signer_seeds = &[&treasury_seeds[..]];
+        config.reserve_insurance_fee(redeem_fee)?;
 
-        token::transfer(
-            CpiContext::new_with_signer(
-                cpi_program,
-                transfer_cpi_account,
-                signer_seeds,
-            ),
-            usdc_to_withdraw
-        )?;
-        user_collateral.usdc_deposit = user_collateral.usdc_deposit.checked_sub(usdc_to_withdraw).ok_or(ErrorCode::ArithmeticOverflow)?;
-        user_collateral.inrc_minted = user_collateral.inrc_minted.checked_sub(amount_inrc).ok_or(ErrorCode::ArithmeticOverflow)?;
-            
-       Ok(())
+        portfolio.normalized_debt = portfolio.normalized_debt.checked_sub(normalized_repayment).ok_or(ErrorCode::ArithmeticOverflow)?;
+        protocol_state.total_normalized_debt = protocol_state.total_normalized_debt.checked_sub(normalized_repayment).ok_or(ErrorCode::ArithmeticOverflow)?;
+        charge_portfolio_debt(portfolio, ctx.remaining_accounts, clock.unix_timestamp, config, false)?;
+
+        Ok(())
     }
 
-    pub fn liquidate(ctx: Context<Liquidate>, amount_inrc_to_burn: u64) -> Result<()> {
-        let config = & ctx.accounts.config;
-        let user_collateral = &mut ctx.accounts.user_collateral;
-        let liquidator = & ctx.accounts.liquidator;
+    // remaining accounts: four per balance, laid out like
+    // mint_portfolio_inrc. the health factor is read at the high price
+    pub fn liquidate_portfolio(ctx: Context<LiquidatePortfolio>, amount_inrc_to_burn: u64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        let collateral_type = & ctx.accounts.collateral_type;
+        let portfolio = &mut ctx.accounts.portfolio;
+        let collateral_mint = ctx.accounts.collateral_mint.key();
         let clock = Clock::get()?;
 
-        let usdc_inr_price = get_pyth_price(&ctx.accounts.usdc_inr_price_feed.to_account_info(), clock.unix_timestamp, MAX_AGE, TARGET_PRICE_DECIMALS)?;
+        if config.is_paused(PAUSE_LIQUIDATE) {
+            return err!(ErrorCode::LiquidationPaused);
+        }
+
+        config.accrue_stability_fee(protocol_state.total_normalized_debt, clock.unix_timestamp)?;
 
-        let usdc_value_in_inr = (user_collateral.usdc_deposit as u128)
-            .checked_mul(usdc_inr_price)
-            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        let normalized_repayment = config.normalized_repayment(portfolio.normalized_debt, amount_inrc_to_burn)?;
+        config.check_position_debt(portfolio.normalized_debt.checked_sub(normalized_repayment).ok_or(ErrorCode::ArithmeticOverflow)?)?;
 
-        let health_factor = if user_collateral.inrc_minted > 0 {
-            usdc_value_in_inr
-                .checked_mul(100)
-                .ok_or(ErrorCode::ArithmeticOverflow)?
-                .checked_div((user_collateral.inrc_minted as u128)
-                    .checked_mul(10u128.pow((TARGET_PRICE_DECIMALS - MINT_DECIMAL as i32) as u32))
-                    .ok_or(ErrorCode::ArithmeticOverflow)?
-                )
-                .ok_or(ErrorCode::ArithmeticOverflow)?
-        } else {
-            u128::MAX 
-        };
+        let inrc_debt = config.debt_from_normalized(portfolio.normalized_debt)?;
 
-        if health_factor >= config.liquidation_threshold as u128 {
+        // the whole portfolio is weighted by liquidation thresholds here
+        let weighted_value = portfolio_weighted_value(&portfolio.balances, ctx.remaining_accounts, clock.unix_timestamp, config, config.liquidation_price_mode, |oracle_price| Ok(oracle_price.high()), |collateral_type| collateral_type.liquidation_threshold)?;
+
+        if health_factor(weighted_value, inrc_debt)? >= 100 {
             return err!(ErrorCode::AboveMinHealthFactor);
         }
 
-        if amount_inrc_to_burn > user_collateral.inrc_minted {
+        // the close factor works on the plain collateral ratio, like for positions
+        let collateral_value = portfolio_weighted_value(&portfolio.balances, ctx.remaining_accounts, clock.unix_timestamp, config, PriceMode::Spot, |oracle_price| Ok(oracle_price.price), |_| 100)?;
+
+        if amount_inrc_to_burn > config.max_liquidation_amount(inrc_debt, health_factor(collateral_value, inrc_debt)?)? {
             return err!(ErrorCode::LiquidationAmountTooHigh);
         }
 
-        let usdc_to_liquidator = (amount_inrc_to_burn as u128)
-            .checked_mul(100 + config.liquidation_bonus as u128) //bonus is applied here
-            .ok_or(ErrorCode::ArithmeticOverflow)?
-            .checked_mul(10u128.pow((TARGET_PRICE_DECIMALS - MINT_DECIMAL as i32) as u32)) 
-            .ok_or(ErrorCode::ArithmeticOverflow)?
-            .checked_div(100) 
+        let collateral_price = get_price(&ctx.accounts.price_feed.to_account_info(), &collateral_type.price_feed_id, recorded_keeper_feed(ctx.accounts.keeper_feed.as_deref(), &collateral_type.keeper_feed)?, clock.unix_timestamp, config)?.price;
+
+        let collateral_to_liquidator = (inrc_value_in_collateral(amount_inrc_to_burn, collateral_type.decimals, collateral_price)? as u128)
+            .checked_mul(100 + collateral_type.liquidation_bonus as u128)
             .ok_or(ErrorCode::ArithmeticOverflow)?
-            .checked_div(usdc_inr_price) 
+            .checked_div(100)
             .ok_or(ErrorCode::ArithmeticOverflow)?
         as u64;
 
-        if usdc_to_liquidator > user_collateral.usdc_deposit {
+        if collateral_to_liquidator > portfolio.balance_of(&collateral_mint) {
             return err!(ErrorCode::InsufficientCollateralForLiquidation);
         }
 
+        // part of the liquidation penalty is kept for the insurance vault
+        let liquidation_penalty = collateral_to_liquidator - inrc_value_in_collateral(amount_inrc_to_burn, collateral_type.decimals, collateral_price)?;
+        let collateral_to_insurance = config.insurance_share(&collateral_mint, liquidation_penalty);
+
+        debit_portfolio_balance(portfolio, &collateral_mint, collateral_to_liquidator)?;
+        protocol_state.debit_collateral(&collateral_mint, collateral_to_liquidator)?;
+
         let burn_accounts = Burn {
             from: ctx.accounts.liquidator_inrc_account.to_account_info(),
             mint: ctx.accounts.inrc_mint.to_account_info(),
-            authority: liquidator.to_account_info(),
+            authority: ctx.accounts.liquidator.to_account_info(),
         };
 
         let cpi_program = ctx.accounts.token_program.to_account_info();
@@ -286,8 +2384,8 @@ pub mod contract_inrc {
         )?;
 
         let transfer_cpi_account = Transfer {
-            from: ctx.accounts.treasury_usdc_account.to_account_info(), 
-            to: ctx.accounts.liquidator_usdc_account.to_account_info(),
+            from: ctx.accounts.collateral_vault.to_account_info(),
+            to: ctx.accounts.liquidator_collateral_account.to_account_info(),
             authority: ctx.accounts.treasury_authority.to_account_info(),
         };
 
@@ -296,59 +2394,556 @@ pub mod contract_inrc {
 
         token::transfer(
             CpiContext::new_with_signer(
-                cpi_program,
+                cpi_program.clone(),
                 transfer_cpi_account,
                 signer_seeds,
             ),
-            usdc_to_liquidator
+            collateral_to_liquidator - collateral_to_insurance
         )?;
 
-        user_collateral.usdc_deposit = user_collateral.usdc_deposit
-        .checked_sub(usdc_to_liquidator)
+        if collateral_to_insurance > 0 {
+            let insurance_transfer_account = Transfer {
+                from: ctx.accounts.collateral_vault.to_account_info(),
+                to: ctx.accounts.insurance_vault.to_account_info(),
+                authority: ctx.accounts.treasury_authority.to_account_info(),
+            };
+
+            token::transfer(
+                CpiContext::new_with_signer(
+                    cpi_program,
+                    insurance_transfer_account,
+                    signer_seeds,
+                ),
+                collateral_to_insurance
+            )?;
+        }
+
+        portfolio.normalized_debt = portfolio.normalized_debt
+        .checked_sub(normalized_repayment)
         .ok_or(ErrorCode::ArithmeticOverflow)?;
 
-        user_collateral.inrc_minted = user_collateral.inrc_minted
-        .checked_sub(amount_inrc_to_burn)
+        protocol_state.total_normalized_debt = protocol_state.total_normalized_debt
+        .checked_sub(normalized_repayment)
         .ok_or(ErrorCode::ArithmeticOverflow)?;
+        charge_portfolio_debt(portfolio, ctx.remaining_accounts, clock.unix_timestamp, config, false)?;
+        Ok(())
+    }
+
+    // a portfolio whose collateral no longer covers its debt is wound down one
+    // collateral at a time. the keeper takes the whole balance at the
+    // liquidation bonus, and once no collateral is left the remaining debt is
+    // bad debt, covered like a position's. the remaining accounts are four per
+    // balance, laid out like mint_portfolio_inrc
+    pub fn settle_portfolio_bad_debt(ctx: Context<SettlePortfolioBadDebt>, max_insurance_inrc: u64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        let collateral_type = & ctx.accounts.collateral_type;
+        let portfolio = &mut ctx.accounts.portfolio;
+        let collateral_mint = ctx.accounts.collateral_mint.key();
+        let clock = Clock::get()?;
+
+        if config.is_paused(PAUSE_LIQUIDATE) {
+            return err!(ErrorCode::LiquidationPaused);
+        }
+
+        config.accrue_stability_fee(protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let inrc_debt = config.debt_from_normalized(portfolio.normalized_debt)?;
+        let collateral_value = portfolio_weighted_value(&portfolio.balances, ctx.remaining_accounts, clock.unix_timestamp, config, PriceMode::Spot, |oracle_price| Ok(oracle_price.high()), |_| 100)?;
+
+        if collateral_value >= inrc_debt as u128 {
+            return err!(ErrorCode::PositionNotUnderwater);
+        }
+
+        let collateral_price = get_price(&ctx.accounts.price_feed.to_account_info(), &collateral_type.price_feed_id, recorded_keeper_feed(ctx.accounts.keeper_feed.as_deref(), &collateral_type.keeper_feed)?, clock.unix_timestamp, config)?.high();
+        let collateral_to_keeper = portfolio.balance_of(&collateral_mint);
+
+        let inrc_to_burn = collateral_value_in_inrc(collateral_to_keeper, collateral_type.decimals, collateral_price)?
+            .checked_mul(100)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_div(100 + collateral_type.liquidation_bonus as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+        as u64;
+        let normalized_repayment = config.normalized_repayment(portfolio.normalized_debt, inrc_to_burn)?;
+
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let treasury_seeds = &[SEED_TREASURY_AUTHORITY,&[config.treasury_authority_bump]];
+        let signer_seeds = &[&treasury_seeds[..]];
+
+        if inrc_to_burn > 0 {
+            let burn_accounts = Burn {
+                from: ctx.accounts.keeper_inrc_account.to_account_info(),
+                mint: ctx.accounts.inrc_mint.to_account_info(),
+                authority: ctx.accounts.keeper.to_account_info(),
+            };
+
+            token::burn(
+                CpiContext::new(
+                    cpi_program.clone(),
+                    burn_accounts,
+                ),
+                inrc_to_burn
+            )?;
+        }
+
+        if collateral_to_keeper > 0 {
+            let transfer_cpi_account = Transfer {
+                from: ctx.accounts.collateral_vault.to_account_info(),
+                to: ctx.accounts.keeper_collateral_account.to_account_info(),
+                authority: ctx.accounts.treasury_authority.to_account_info(),
+            };
+
+            token::transfer(
+                CpiContext::new_with_signer(
+                    cpi_program.clone(),
+                    transfer_cpi_account,
+                    signer_seeds,
+                ),
+                collateral_to_keeper
+            )?;
+
+            debit_portfolio_balance(portfolio, &collateral_mint, collateral_to_keeper)?;
+            protocol_state.debit_collateral(&collateral_mint, collateral_to_keeper)?;
+        }
+
+        portfolio.normalized_debt = portfolio.normalized_debt.checked_sub(normalized_repayment).ok_or(ErrorCode::ArithmeticOverflow)?;
+        protocol_state.total_normalized_debt = protocol_state.total_normalized_debt.checked_sub(normalized_repayment).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        // with the last collateral gone, whatever debt is left is bad debt
+        let mut bad_debt = 0;
+        if portfolio.balances.iter().all(|balance| balance.amount == 0) {
+            bad_debt = config.debt_from_normalized(portfolio.normalized_debt)?;
+            protocol_state.total_normalized_debt = protocol_state.total_normalized_debt.checked_sub(portfolio.normalized_debt).ok_or(ErrorCode::ArithmeticOverflow)?;
+            portfolio.normalized_debt = 0;
+        }
+
+        charge_portfolio_debt(portfolio, ctx.remaining_accounts, clock.unix_timestamp, config, false)?;
+
+        // insurance usdc buys back as much of the bad debt as it can before
+        // surplus is used, and only what is left after that is socialized
+        let mut insurance_inrc = 0;
+
+        if bad_debt > 0 && ctx.accounts.insurance_vault.amount > 0 {
+            let usdc_price = get_price(&ctx.accounts.usdc_price_feed.to_account_info(), &config.usdc_price_feed_id, recorded_keeper_feed(ctx.accounts.usdc_keeper_feed.as_deref(), &config.usdc_keeper_feed)?, clock.unix_timestamp, config)?.price;
+            let insurance_usdc;
+            (insurance_inrc, insurance_usdc) = insurance_draw(
+                ctx.accounts.insurance_vault.amount,
+                config.usdc_decimals,
+                usdc_price,
+                bad_debt,
+            )?;
+
+            if insurance_inrc > max_insurance_inrc {
+                return err!(ErrorCode::InsuranceDrawTooHigh);
+            }
+
+            if insurance_inrc > 0 {
+                let burn_accounts = Burn {
+                    from: ctx.accounts.keeper_inrc_account.to_account_info(),
+                    mint: ctx.accounts.inrc_mint.to_account_info(),
+                    authority: ctx.accounts.keeper.to_account_info(),
+                };
+
+                token::burn(
+                    CpiContext::new(
+                        cpi_program.clone(),
+                        burn_accounts,
+                    ),
+                    insurance_inrc
+                )?;
+
+                let transfer_cpi_account = Transfer {
+                    from: ctx.accounts.insurance_vault.to_account_info(),
+                    to: ctx.accounts.keeper_usdc_account.to_account_info(),
+                    authority: ctx.accounts.treasury_authority.to_account_info(),
+                };
+
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        cpi_program,
+                        transfer_cpi_account,
+                        signer_seeds,
+                    ),
+                    insurance_usdc
+                )?;
+                msg!("Insurance paid {} usdc to cover {} inrc of bad debt", insurance_usdc, insurance_inrc);
+            }
+        }
+
+        let uncovered = config.cover_from_surplus(bad_debt - insurance_inrc);
+        config.socialize_bad_debt(protocol_state.total_normalized_debt, uncovered)?;
+        msg!("Settled portfolio of {} with {} inrc of bad debt", portfolio.owner, bad_debt);
+
+        Ok(())
+    }
+
+    // read only, returns the value of all collateral over all debt as a
+    // percentage, the same scale as a position health factor
+    // anyone can record the spot price of a collateral type in the protocol
+    // state. get_collateral_ratio values collateral at these prices
+    pub fn update_collateral_price(ctx: Context<UpdateCollateralPrice>) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let collateral_type = &ctx.accounts.collateral_type;
+        let collateral_price = get_price(&ctx.accounts.price_feed.to_account_info(), &collateral_type.price_feed_id, recorded_keeper_feed(ctx.accounts.keeper_feed.as_deref(), &collateral_type.keeper_feed)?, clock.unix_timestamp, &ctx.accounts.config)?.price;
+        ctx.accounts.protocol_state.record_collateral_price(&collateral_type.mint, collateral_price, clock.unix_timestamp)?;
+        msg!("Recorded price {} for {}", collateral_price, collateral_type.mint);
+
         Ok(())
     }
 
+    pub fn get_collateral_ratio(ctx: Context<GetCollateralRatio>) -> Result<u128> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.accrue_stability_fee(ctx.accounts.protocol_state.total_normalized_debt, clock.unix_timestamp)?;
+
+        let config = &ctx.accounts.config;
+        let protocol_state = &ctx.accounts.protocol_state;
+        let total_collateral_value = protocol_collateral_value(&protocol_state.collateral_totals)?;
+        let total_inrc_debt = config.debt_from_normalized(protocol_state.total_normalized_debt)?;
+        let collateral_ratio = health_factor(total_collateral_value, total_inrc_debt)?;
+        let oldest_price = protocol_state.collateral_totals
+            .iter()
+            .filter(|collateral_total| collateral_total.amount > 0)
+            .map(|collateral_total| collateral_total.priced_at)
+            .min()
+            .unwrap_or(clock.unix_timestamp);
+        msg!("Collateral value {} inrc, debt {} inrc, ratio {}, oldest price from {}", total_collateral_value, total_inrc_debt, collateral_ratio, oldest_price);
+
+        Ok(collateral_ratio)
+    }
+
+}
+
+// prices are scaled to TARGET_PRICE_DECIMALS and inrc uses MINT_DECIMAL
+// decimals, so both scales have to be taken out of the product
+fn collateral_value_in_inrc(amount_collateral: u64, collateral_decimals: u8, collateral_price: u128) -> Result<u128> {
+    let value = (amount_collateral as u128)
+        .checked_mul(collateral_price)
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        .checked_mul(10u128.pow(MINT_DECIMAL as u32))
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        .checked_div(10u128.pow(collateral_decimals as u32 + TARGET_PRICE_DECIMALS as u32))
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    Ok(value)
+}
+
+fn fee_amount(amount_inrc: u64, fee_bps: u16) -> Result<u64> {
+    let fee = (amount_inrc as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        .checked_div(BPS_DENOMINATOR as u128)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    Ok(fee as u64)
+}
+
+// inrc the insurance vault can buy back, at most `max_inrc`, and the usdc
+// it pays out for it
+fn insurance_draw(insurance_balance: u64, usdc_decimals: u8, usdc_price: u128, max_inrc: u64) -> Result<(u64, u64)> {
+    let insurance_value = collateral_value_in_inrc(insurance_balance, usdc_decimals, usdc_price)?;
+    let inrc_covered = (max_inrc as u128).min(insurance_value) as u64;
+    let usdc_paid = inrc_value_in_collateral(inrc_covered, usdc_decimals, usdc_price)?.min(insurance_balance);
+
+    Ok((inrc_covered, usdc_paid))
+}
+
+fn inrc_value_in_collateral(amount_inrc: u64, collateral_decimals: u8, collateral_price: u128) -> Result<u64> {
+    let value = (amount_inrc as u128)
+        .checked_mul(10u128.pow(collateral_decimals as u32 + TARGET_PRICE_DECIMALS as u32))
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        .checked_div(10u128.pow(MINT_DECIMAL as u32))
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        .checked_div(collateral_price)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    u64::try_from(value).map_err(|_| error!(ErrorCode::ArithmeticOverflow))
+}
+
+// collateral value as a percentage of debt, comparable with the
+// liquidation threshold and min health factor of a CollateralType
+fn health_factor(collateral_value_in_inrc: u128, inrc_debt: u64) -> Result<u128> {
+    if inrc_debt == 0 {
+        return Ok(u128::MAX);
+    }
+
+    let health_factor = collateral_value_in_inrc
+        .checked_mul(100)
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        .checked_div(inrc_debt as u128)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    Ok(health_factor)
+}
+
+// takes `amount` of `collateral_mint` out of the portfolio, dropping the
+// balance once it is empty so it no longer needs pricing
+fn debit_portfolio_balance(portfolio: &mut Portfolio, collateral_mint: &Pubkey, amount: u64) -> Result<()> {
+    let index = portfolio.balances
+        .iter()
+        .position(|balance| balance.collateral_mint == *collateral_mint)
+        .ok_or(ErrorCode::InsufficientFunds)?;
+
+    let balance = &mut portfolio.balances[index];
+    balance.amount = balance.amount.checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+
+    Ok(())
 }
 
-   
-fn get_pyth_price(
-    price_account_info: &AccountInfo,
+// each balance counts for value * 100 / weight of its collateral type, so
+// with a threshold as the weight the sum can be compared with the portfolio
+// debt directly. the remaining accounts carry a (CollateralType, price
+// feed, keeper feed, price history) quadruple per balance, in order
+fn portfolio_weighted_value(
+    balances: &[PortfolioBalance],
+    remaining_accounts: &[AccountInfo],
     current_timestamp: i64,
-    max_age: u64,
-    target_decimals: i32,
+    config: &Config,
+    mode: PriceMode,
+    price: fn(&OraclePrice) -> Result<u128>,
+    weight: fn(&CollateralType) -> u64,
     ) -> Result<u128> {
-    let price_feed = SolanaPriceAccount::account_info_to_feed(price_account_info)
-        .map_err(|_| ErrorCode::InvalidPrice)?;
-
-    let current_price = price_feed
-        .get_price_no_older_than(current_timestamp, max_age)
-        .ok_or(ErrorCode::InvalidPrice)?;
-    let price_val = current_price.price;
-    let price_expo = current_price.expo;
-
-    let scaled_price: u128;
-    if price_expo < target_decimals {
-        let diff = (target_decimals - price_expo) as u32;
-        scaled_price = (price_val as u128)
-            .checked_mul(10u128.pow(diff))
+    if remaining_accounts.len() != balances.len() * PORTFOLIO_ACCOUNTS_PER_BALANCE {
+        return err!(ErrorCode::PortfolioAccountCountMismatch);
+    }
+
+    let mut weighted_value: u128 = 0;
+    for (balance, accounts) in balances.iter().zip(remaining_accounts.chunks(PORTFOLIO_ACCOUNTS_PER_BALANCE)) {
+        let collateral_type = load_collateral_type(&accounts[0])?;
+
+        if collateral_type.mint != balance.collateral_mint || accounts[1].key() != collateral_type.price_feed {
+            return err!(ErrorCode::InvalidPortfolioAccounts);
+        }
+
+        let keeper_feed = load_keeper_feed(&accounts[2], &collateral_type)?;
+        // the history is only read in twap mode
+        let price_history = match mode {
+            PriceMode::Twap => load_price_history(&accounts[3], &collateral_type.mint)?,
+            _ => None,
+        };
+        let collateral_price = price(&get_price_with_mode(&accounts[1], &collateral_type.price_feed_id, keeper_feed.as_ref(), price_history.as_ref(), current_timestamp, config, mode)?)?;
+
+        weighted_value = collateral_value_in_inrc(balance.amount, collateral_type.decimals, collateral_price)?
+            .checked_mul(100)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_div(weight(&collateral_type) as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_add(weighted_value)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+    }
+
+    Ok(weighted_value)
+}
+
+// spreads the portfolio debt over its collateral types by what each balance
+// can borrow against, weighted like portfolio_weighted_value with the min
+// health factor, and moves the difference onto each type's
+// total_normalized_debt. the CollateralTypes in the remaining accounts have
+// to be writable. with `check_ceiling` a type that is charged more debt has
+// to stay under its debt ceiling
+fn charge_portfolio_debt(
+    portfolio: &mut Portfolio,
+    remaining_accounts: &[AccountInfo],
+    current_timestamp: i64,
+    config: &Config,
+    check_ceiling: bool,
+    ) -> Result<()> {
+    if remaining_accounts.len() != portfolio.balances.len() * PORTFOLIO_ACCOUNTS_PER_BALANCE {
+        return err!(ErrorCode::PortfolioAccountCountMismatch);
+    }
+
+    let mut collateral_types = Vec::with_capacity(portfolio.balances.len());
+    let mut weighted_values = Vec::with_capacity(portfolio.balances.len());
+    let mut total_weighted_value: u128 = 0;
+    for (balance, accounts) in portfolio.balances.iter().zip(remaining_accounts.chunks(PORTFOLIO_ACCOUNTS_PER_BALANCE)) {
+        let collateral_type = load_collateral_type(&accounts[0])?;
+
+        if !accounts[0].is_writable || collateral_type.mint != balance.collateral_mint || accounts[1].key() != collateral_type.price_feed {
+            return err!(ErrorCode::InvalidPortfolioAccounts);
+        }
+
+        let keeper_feed = load_keeper_feed(&accounts[2], &collateral_type)?;
+        let collateral_price = get_price(&accounts[1], &collateral_type.price_feed_id, keeper_feed.as_ref(), current_timestamp, config)?.price;
+        let weighted_value = collateral_value_in_inrc(balance.amount, collateral_type.decimals, collateral_price)?
+            .checked_mul(100)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_div(collateral_type.min_health_factor as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        total_weighted_value = total_weighted_value.checked_add(weighted_value).ok_or(ErrorCode::ArithmeticOverflow)?;
+        weighted_values.push(weighted_value);
+        collateral_types.push(collateral_type);
+    }
+
+    // the largest balance takes the rounding remainder, so the charges add
+    // up to the portfolio debt exactly
+    let mut charges = Vec::with_capacity(weighted_values.len());
+    for weighted_value in &weighted_values {
+        let charge = match total_weighted_value {
+            0 => 0,
+            _ => ((portfolio.normalized_debt as u128)
+                .checked_mul(*weighted_value)
+                .ok_or(ErrorCode::ArithmeticOverflow)?
+                / total_weighted_value) as u64,
+        };
+        charges.push(charge);
+    }
+    if let Some(largest) = (0..weighted_values.len()).max_by_key(|&index| weighted_values[index]) {
+        let charged: u64 = charges.iter().sum();
+        charges[largest] += portfolio.normalized_debt - charged;
+    }
+
+    for (index, balance) in portfolio.balances.iter_mut().enumerate() {
+        let normalized_debt = charges[index];
+        let collateral_type = &mut collateral_types[index];
+        collateral_type.total_normalized_debt = collateral_type.total_normalized_debt
+            .checked_sub(balance.normalized_debt)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_add(normalized_debt)
             .ok_or(ErrorCode::ArithmeticOverflow)?;
-    } else if price_expo > target_decimals {
-        let diff = (price_expo - target_decimals) as u32;
-        scaled_price = (price_val as u128)
-            .checked_div(10u128.pow(diff))
+
+        if check_ceiling
+            && normalized_debt > balance.normalized_debt
+            && config.debt_from_normalized(collateral_type.total_normalized_debt)? > collateral_type.debt_ceiling {
+            return err!(ErrorCode::DebtCeilingExceeded);
+        }
+
+        balance.normalized_debt = normalized_debt;
+        collateral_type.try_serialize(&mut &mut remaining_accounts[index * PORTFOLIO_ACCOUNTS_PER_BALANCE].data.borrow_mut()[..])?;
+    }
+
+    portfolio.prune_balances();
+
+    Ok(())
+}
+
+// values every collateral total at the last price recorded for it. a
+// type holding collateral that was never priced can't be valued
+fn protocol_collateral_value(collateral_totals: &[CollateralTotal]) -> Result<u128> {
+    let mut total_value: u128 = 0;
+    for collateral_total in collateral_totals.iter().filter(|collateral_total| collateral_total.amount > 0) {
+        if collateral_total.priced_at == 0 {
+            return err!(ErrorCode::InvalidPrice);
+        }
+
+        total_value = collateral_value_in_inrc(collateral_total.amount, collateral_total.decimals, collateral_total.price)?
+            .checked_add(total_value)
             .ok_or(ErrorCode::ArithmeticOverflow)?;
-    } else {
-        scaled_price = price_val as u128;
     }
 
-    Ok(scaled_price)
+    Ok(total_value)
+}
+
+// moves the deposit's collateral gains since its last snapshot into
+// pending_gains, compounds the deposit and snapshots the pool again. gains
+// made in the scale after the snapshot count 1 / POOL_SCALE_FACTOR as much,
+// and later scales round to nothing
+fn settle_stability_deposit(
+    stability_pool: &StabilityPool,
+    stability_deposit: &mut StabilityDeposit,
+    depositor_epoch: &AccountInfo,
+    depositor_next_scale: &AccountInfo,
+    current_epoch: &StabilityPoolEpoch,
+    ) -> Result<()> {
+    if stability_deposit.initial_amount > 0 {
+        let epoch_sums = load_stability_pool_epoch(depositor_epoch)?;
+        let next_scale_sums = if depositor_next_scale.owner == &crate::ID {
+            Some(load_stability_pool_epoch(depositor_next_scale)?)
+        } else {
+            None
+        };
+
+        for sum in epoch_sums.sums.iter() {
+            let next_scale_sum = next_scale_sums.as_ref().map_or(0, |sums| sums.sum_of(&sum.collateral_mint));
+            let sum_increase = sum.sum
+                .checked_sub(stability_deposit.snapshot_of(&sum.collateral_mint))
+                .ok_or(ErrorCode::ArithmeticOverflow)?
+                .checked_add(next_scale_sum / POOL_SCALE_FACTOR)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+            let gain = (stability_deposit.initial_amount as u128)
+                .checked_mul(sum_increase)
+                .ok_or(ErrorCode::ArithmeticOverflow)?
+                .checked_div(stability_deposit.product_snapshot)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+            if gain > 0 {
+                stability_deposit.credit_gain(sum.collateral_mint, u64::try_from(gain).map_err(|_| ErrorCode::ArithmeticOverflow)?)?;
+            }
+        }
+    }
+
+    stability_deposit.initial_amount = stability_pool.compounded_deposit(stability_deposit);
+    stability_deposit.product_snapshot = stability_pool.product;
+    stability_deposit.epoch = stability_pool.epoch;
+    stability_deposit.scale = stability_pool.scale;
+    stability_deposit.sum_snapshots = current_epoch.sums.clone();
+
+    Ok(())
+}
+
+fn load_stability_pool_epoch(account_info: &AccountInfo) -> Result<StabilityPoolEpoch> {
+    if account_info.owner != &crate::ID {
+        return err!(ErrorCode::InvalidStabilityPoolEpoch);
+    }
+
+    StabilityPoolEpoch::try_deserialize(&mut &account_info.data.borrow()[..])
+        .map_err(|_| error!(ErrorCode::InvalidStabilityPoolEpoch))
+}
+
+fn load_user_collateral(account_info: &AccountInfo) -> Result<UserCollateral> {
+    if account_info.owner != &crate::ID {
+        return err!(ErrorCode::InvalidRedemptionOrder);
+    }
+
+    UserCollateral::try_deserialize(&mut &account_info.data.borrow()[..])
+}
+
+fn load_collateral_type(account_info: &AccountInfo) -> Result<CollateralType> {
+    if account_info.owner != &crate::ID {
+        return err!(ErrorCode::InvalidPortfolioAccounts);
+    }
+
+    CollateralType::try_deserialize(&mut &account_info.data.borrow()[..])
+}
+
+// the keeper feed recorded on the collateral type. the account is ignored
+// when none is recorded, the program id can stand in for it then
+fn load_keeper_feed(account_info: &AccountInfo, collateral_type: &CollateralType) -> Result<Option<KeeperFeed>> {
+    if collateral_type.keeper_feed == Pubkey::default() {
+        return Ok(None);
+    }
+
+    if account_info.key() != collateral_type.keeper_feed {
+        return err!(ErrorCode::KeeperFeedRequired);
+    }
+
+    if account_info.owner != &crate::ID {
+        return err!(ErrorCode::InvalidPortfolioAccounts);
+    }
+
+    let keeper_feed = KeeperFeed::try_deserialize(&mut &account_info.data.borrow()[..])?;
+    Ok(Some(keeper_feed))
 }
 
+// the program id in place of a price history means there is none
+fn load_price_history(account_info: &AccountInfo, mint: &Pubkey) -> Result<Option<PriceHistory>> {
+    if account_info.key() == crate::ID {
+        return Ok(None);
+    }
+
+    if account_info.owner != &crate::ID {
+        return err!(ErrorCode::InvalidPortfolioAccounts);
+    }
+
+    let price_history = PriceHistory::try_deserialize(&mut &account_info.data.borrow()[..])?;
+    if price_history.mint != *mint {
+        return err!(ErrorCode::InvalidPortfolioAccounts);
+    }
+
+    Ok(Some(price_history))
+}
 
 #[error_code]
 pub enum ErrorCode {
@@ -370,4 +2965,80 @@ pub enum ErrorCode {
     InsufficientFunds,
     #[msg("Price Overflow")]
     ArithmeticOverflow,
+    #[msg("Invalid risk parameters")]
+    InvalidRiskParameters,
+    #[msg("Config update is still timelocked")]
+    ConfigUpdateTimelocked,
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+    #[msg("Minting is paused")]
+    MintPaused,
+    #[msg("Withdrawals are paused")]
+    WithdrawPaused,
+    #[msg("Liquidations are paused")]
+    LiquidationPaused,
+    #[msg("Deposits are paused")]
+    DepositPaused,
+    #[msg("Repayments are paused")]
+    RepayPaused,
+    #[msg("Collateral does not match the position's collateral type")]
+    CollateralTypeMismatch,
+    #[msg("Debt ceiling exceeded")]
+    DebtCeilingExceeded,
+    #[msg("Portfolio cannot hold more collateral types")]
+    PortfolioFull,
+    #[msg("Remaining accounts do not match the portfolio balances")]
+    InvalidPortfolioAccounts,
+    #[msg("Protocol debt ceiling exceeded")]
+    GlobalDebtCeilingExceeded,
+    #[msg("Position debt is below the minimum")]
+    BelowMinPositionDebt,
+    #[msg("Too many collateral types registered")]
+    TooManyCollateralTypes,
+    #[msg("Not allowed in the current liquidation mode")]
+    LiquidationModeMismatch,
+    #[msg("Auction cannot be reset yet")]
+    AuctionNotExpired,
+    #[msg("Auction price is above the bid limit")]
+    AuctionPriceTooHigh,
+    #[msg("Position is not underwater")]
+    PositionNotUnderwater,
+    #[msg("Insurance draw is above the given maximum")]
+    InsuranceDrawTooHigh,
+    #[msg("Not enough inrc in the stability pool")]
+    InsufficientStabilityPoolDeposits,
+    #[msg("Invalid stability pool epoch account")]
+    InvalidStabilityPoolEpoch,
+    #[msg("PSM capacity exceeded")]
+    PsmCapacityExceeded,
+    #[msg("Not enough usdc in the PSM reserve")]
+    InsufficientPsmReserve,
+    #[msg("Swap output below the minimum")]
+    SlippageExceeded,
+    #[msg("Positions have to be redeemed in order, lowest health factor first")]
+    InvalidRedemptionOrder,
+    #[msg("Price update is for a different feed")]
+    PriceFeedIdMismatch,
+    #[msg("Price update is not fully verified")]
+    PriceNotFullyVerified,
+    #[msg("Price confidence band is too wide")]
+    PriceConfidenceTooWide,
+    #[msg("Price sources disagree by more than the allowed deviation")]
+    OracleDeviationTooHigh,
+    #[msg("The keeper feed of this asset has to be passed")]
+    KeeperFeedRequired,
+    #[msg("A keeper price can't be used for this action")]
+    KeeperPriceNotAllowed,
+    #[msg("Price history does not cover the twap window")]
+    PriceHistoryUnavailable,
+    #[msg("Price was recorded too recently")]
+    PriceObservationTooSoon,
+    #[msg("Price is older than the maximum age")]
+    PriceStale,
+    #[msg("Account did not pay for the auction")]
+    AuctionPayerMismatch,
+    #[msg("Sorted positions list is full")]
+    SortedPositionsFull,
+    #[msg("Expected four remaining accounts for every portfolio balance")]
+    PortfolioAccountCountMismatch,
 }
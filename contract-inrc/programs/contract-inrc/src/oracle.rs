@@ -0,0 +1,319 @@
+use anchor_lang::prelude::*;
+use pyth_sdk_solana::state::SolanaPriceAccount;
+
+use crate::{
+    Config, ErrorCode, KeeperFeed, PriceHistory, PriceMode, BPS_DENOMINATOR, MAX_AGE, PRICE_UPDATE_V2_DISCRIMINATOR, PYTH_RECEIVER_PROGRAM_ID, TARGET_PRICE_DECIMALS,
+};
+
+// layout of the pyth receiver's PriceUpdateV2 account. the receiver sdk
+// builds against a different anchor-lang, so the account is decoded here
+#[derive(AnchorDeserialize)]
+pub enum VerificationLevel {
+    Partial { num_signatures: u8 },
+    Full,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct PriceFeedMessage {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+    pub prev_publish_time: i64,
+    pub ema_price: i64,
+    pub ema_conf: u64,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct PriceUpdateV2 {
+    pub write_authority: Pubkey,
+    pub verification_level: VerificationLevel,
+    pub price_message: PriceFeedMessage,
+    pub posted_slot: u64,
+}
+
+impl PriceUpdateV2 {
+    pub fn try_from_account_info(account_info: &AccountInfo) -> Result<Self> {
+        if account_info.owner != &PYTH_RECEIVER_PROGRAM_ID {
+            return err!(ErrorCode::InvalidPrice);
+        }
+
+        let data = account_info.data.borrow();
+        if data.len() < 8 || data[..8] != PRICE_UPDATE_V2_DISCRIMINATOR {
+            return err!(ErrorCode::InvalidPrice);
+        }
+
+        PriceUpdateV2::deserialize(&mut &data[8..]).map_err(|_| error!(ErrorCode::InvalidPrice))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PriceSource {
+    Primary,
+    Keeper,
+}
+
+// a price and its confidence band, both scaled to the same decimals, and
+// the source it was read from
+#[derive(Clone, Copy)]
+pub struct OraclePrice {
+    pub price: u128,
+    pub conf: u128,
+    pub source: PriceSource,
+}
+
+impl OraclePrice {
+    // bottom of the band, collateral is valued here for minting and withdrawals
+    pub fn low(&self) -> u128 {
+        self.price.saturating_sub(self.conf)
+    }
+
+    // top of the band, collateral is valued here when deciding liquidations
+    pub fn high(&self) -> u128 {
+        self.price.saturating_add(self.conf)
+    }
+
+    // low(), but only for a primary price. new debt and withdrawals wait for
+    // the primary, the keeper fallback is there to keep liquidations going
+    pub fn borrow_price(&self) -> Result<u128> {
+        if self.source == PriceSource::Keeper {
+            return err!(ErrorCode::KeeperPriceNotAllowed);
+        }
+
+        Ok(self.low())
+    }
+}
+
+// the keeper feed passed for an asset, checked against the one recorded for
+// it. leaving a recorded feed out would skip the deviation check, and a feed
+// that isn't recorded is not used as a fallback
+pub fn recorded_keeper_feed<'a>(keeper_feed: Option<&'a KeeperFeed>, recorded: &Pubkey) -> Result<Option<&'a KeeperFeed>> {
+    if *recorded == Pubkey::default() {
+        return Ok(None);
+    }
+
+    match keeper_feed {
+        Some(keeper_feed) => Ok(Some(keeper_feed)),
+        None => err!(ErrorCode::KeeperFeedRequired),
+    }
+}
+
+// reads the spot price of an asset from its primary price account, scaled
+// to TARGET_PRICE_DECIMALS. when a keeper feed is passed it stands in for a
+// primary that is stale, and a fresh keeper price has to agree with the
+// primary within max_oracle_deviation_bps. any other primary error is
+// returned as is
+pub fn get_price(
+    price_account_info: &AccountInfo,
+    feed_id: &[u8; 32],
+    keeper_feed: Option<&KeeperFeed>,
+    current_timestamp: i64,
+    config: &Config,
+    ) -> Result<OraclePrice> {
+    let primary_price = get_primary_price(price_account_info, feed_id, current_timestamp, false)
+        .and_then(|oracle_price| check_confidence(oracle_price, config.max_price_confidence_bps));
+
+    let keeper_feed = match keeper_feed {
+        Some(keeper_feed) => keeper_feed,
+        None => return primary_price,
+    };
+
+    let keeper_price = get_keeper_price(keeper_feed, current_timestamp, MAX_AGE, TARGET_PRICE_DECIMALS)
+        .and_then(|oracle_price| check_confidence(oracle_price, config.max_price_confidence_bps));
+
+    match (primary_price, keeper_price) {
+        (Ok(primary_price), Ok(keeper_price)) => {
+            check_deviation(&primary_price, &keeper_price, config.max_oracle_deviation_bps)?;
+            Ok(primary_price)
+        }
+        (Ok(primary_price), Err(_)) => Ok(primary_price),
+        (Err(error), Ok(keeper_price)) if error == error!(ErrorCode::PriceStale) => {
+            msg!("Primary price is stale, using keeper feed for {}", keeper_feed.mint);
+            Ok(keeper_price)
+        }
+        (Err(error), _) => Err(error),
+    }
+}
+
+// like get_price, but the price is the one `mode` asks for. the confidence
+// band stays the spot band in twap mode. a keeper price has no ema, so while
+// the primary is stale ema mode reads the keeper spot price
+pub fn get_price_with_mode(
+    price_account_info: &AccountInfo,
+    feed_id: &[u8; 32],
+    keeper_feed: Option<&KeeperFeed>,
+    price_history: Option<&PriceHistory>,
+    current_timestamp: i64,
+    config: &Config,
+    mode: PriceMode,
+    ) -> Result<OraclePrice> {
+    let spot_price = get_price(price_account_info, feed_id, keeper_feed, current_timestamp, config)?;
+
+    match mode {
+        PriceMode::Spot => Ok(spot_price),
+        PriceMode::Ema if spot_price.source == PriceSource::Keeper => Ok(spot_price),
+        PriceMode::Ema => {
+            let ema_price = get_primary_price(price_account_info, feed_id, current_timestamp, true)?;
+            check_confidence(ema_price, config.max_price_confidence_bps)
+        }
+        PriceMode::Twap => {
+            let price_history = price_history.ok_or(ErrorCode::PriceHistoryUnavailable)?;
+
+            Ok(OraclePrice {
+                price: price_history.twap(current_timestamp, config.twap_window)?,
+                ..spot_price
+            })
+        }
+    }
+}
+
+// a type without a feed id still uses a legacy push account
+fn get_primary_price(
+    price_account_info: &AccountInfo,
+    feed_id: &[u8; 32],
+    current_timestamp: i64,
+    ema: bool,
+    ) -> Result<OraclePrice> {
+    if *feed_id == [0u8; 32] {
+        get_pyth_price(price_account_info, current_timestamp, MAX_AGE, TARGET_PRICE_DECIMALS, ema)
+    } else {
+        get_price_update_price(price_account_info, feed_id, current_timestamp, MAX_AGE, TARGET_PRICE_DECIMALS, ema)
+    }
+}
+
+fn check_confidence(oracle_price: OraclePrice, max_confidence_bps: u16) -> Result<OraclePrice> {
+    let max_conf = oracle_price.price
+        .checked_mul(max_confidence_bps as u128)
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        / BPS_DENOMINATOR as u128;
+
+    if oracle_price.conf > max_conf {
+        return err!(ErrorCode::PriceConfidenceTooWide);
+    }
+
+    Ok(oracle_price)
+}
+
+// the deviation is measured against the primary price
+fn check_deviation(primary_price: &OraclePrice, keeper_price: &OraclePrice, max_deviation_bps: u16) -> Result<()> {
+    let deviation = primary_price.price.abs_diff(keeper_price.price)
+        .checked_mul(BPS_DENOMINATOR as u128)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    let max_deviation = primary_price.price
+        .checked_mul(max_deviation_bps as u128)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    if deviation > max_deviation {
+        return err!(ErrorCode::OracleDeviationTooHigh);
+    }
+
+    Ok(())
+}
+
+pub fn get_pyth_price(
+    price_account_info: &AccountInfo,
+    current_timestamp: i64,
+    max_age: u64,
+    target_decimals: i32,
+    ema: bool,
+    ) -> Result<OraclePrice> {
+    let price_feed = SolanaPriceAccount::account_info_to_feed(price_account_info)
+        .map_err(|_| ErrorCode::InvalidPrice)?;
+
+    let current_price = if ema {
+        price_feed.get_ema_price_no_older_than(current_timestamp, max_age)
+    } else {
+        price_feed.get_price_no_older_than(current_timestamp, max_age)
+    }
+    .ok_or(ErrorCode::PriceStale)?;
+
+    scale_price(current_price.price, current_price.conf, current_price.expo, target_decimals)
+}
+
+// reads a PriceUpdateV2 account owned by the pyth receiver program. only
+// fully verified updates for `feed_id` no older than `max_age` are accepted
+pub fn get_price_update_price(
+    price_account_info: &AccountInfo,
+    feed_id: &[u8; 32],
+    current_timestamp: i64,
+    max_age: u64,
+    target_decimals: i32,
+    ema: bool,
+    ) -> Result<OraclePrice> {
+    let price_update = PriceUpdateV2::try_from_account_info(price_account_info)?;
+
+    if !matches!(price_update.verification_level, VerificationLevel::Full) {
+        return err!(ErrorCode::PriceNotFullyVerified);
+    }
+
+    let current_price = price_update.price_message;
+
+    if current_price.feed_id != *feed_id {
+        return err!(ErrorCode::PriceFeedIdMismatch);
+    }
+
+    if current_price.publish_time.saturating_add(max_age as i64) < current_timestamp {
+        return err!(ErrorCode::PriceStale);
+    }
+
+    if ema {
+        return scale_price(current_price.ema_price, current_price.ema_conf, current_price.exponent, target_decimals);
+    }
+
+    scale_price(current_price.price, current_price.conf, current_price.exponent, target_decimals)
+}
+
+pub fn get_keeper_price(
+    keeper_feed: &KeeperFeed,
+    current_timestamp: i64,
+    max_age: u64,
+    target_decimals: i32,
+    ) -> Result<OraclePrice> {
+    if keeper_feed.publish_time.saturating_add(max_age as i64) < current_timestamp {
+        return err!(ErrorCode::PriceStale);
+    }
+
+    let oracle_price = scale_price(keeper_feed.price, keeper_feed.conf, keeper_feed.expo, target_decimals)?;
+
+    Ok(OraclePrice {
+        source: PriceSource::Keeper,
+        ..oracle_price
+    })
+}
+
+fn scale_price(price_val: i64, conf_val: u64, price_expo: i32, target_decimals: i32) -> Result<OraclePrice> {
+    if price_val <= 0 {
+        return err!(ErrorCode::InvalidPrice);
+    }
+
+    Ok(OraclePrice {
+        price: scale_value(price_val as u128, price_expo, target_decimals)?,
+        conf: scale_value(conf_val as u128, price_expo, target_decimals)?,
+        source: PriceSource::Primary,
+    })
+}
+
+fn scale_value(value: u128, price_expo: i32, target_decimals: i32) -> Result<u128> {
+    let scaled_value: u128;
+    if price_expo < target_decimals {
+        let diff = target_decimals
+            .checked_sub(price_expo)
+            .ok_or(ErrorCode::ArithmeticOverflow)? as u32;
+        scaled_value = value
+            .checked_mul(10u128.checked_pow(diff).ok_or(ErrorCode::ArithmeticOverflow)?)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+    } else if price_expo > target_decimals {
+        let diff = price_expo
+            .checked_sub(target_decimals)
+            .ok_or(ErrorCode::ArithmeticOverflow)? as u32;
+        scaled_value = value
+            .checked_div(10u128.checked_pow(diff).ok_or(ErrorCode::ArithmeticOverflow)?)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+    } else {
+        scaled_value = value;
+    }
+
+    Ok(scaled_value)
+}
\ No newline at end of file
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+// collateral and debt taken out of an unhealthy position, sold to bidders
+// at a discount that grows from started_at
+#[account]
+#[derive(InitSpace)]
+pub struct Auction {
+    pub owner: Pubkey,
+    // paid the rent in start_auction and gets it back when the auction closes
+    pub payer: Pubkey,
+    pub position_id: u64,
+    pub collateral_mint: Pubkey,
+    pub collateral_amount: u64,
+    pub normalized_debt: u64,
+    pub started_at: i64,
+    pub bump: u8,
+}
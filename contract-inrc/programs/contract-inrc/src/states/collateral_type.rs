@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+use crate::{ErrorCode, KeeperFeed, MAX_LIQUIDATION_BONUS, MAX_MIN_HEALTH_FACTOR, MAX_SORTED_POSITIONS};
+
+#[account]
+#[derive(InitSpace)]
+pub struct CollateralType {
+    pub mint: Pubkey,
+    pub price_feed: Pubkey,
+    // pyth feed id read from a PriceUpdateV2 account. all zero for a
+    // legacy push price account
+    pub price_feed_id: [u8; 32],
+    // keeper feed that has to be passed with the price feed, the default
+    // pubkey when the collateral type has none
+    pub keeper_feed: Pubkey,
+    pub decimals: u8,
+    pub liquidation_threshold: u64,
+    pub liquidation_bonus: u64,
+    pub min_health_factor: u64,
+    pub debt_ceiling: u64,
+    pub total_normalized_debt: u64,
+    pub sorted_positions: Pubkey,
+    pub bump: u8,
+}
+
+impl CollateralType {
+    pub fn validate_risk_params(&self, min_position_debt: u64) -> Result<()> {
+        require!(
+            self.keeper_feed == Pubkey::default() || self.keeper_feed == KeeperFeed::address(&self.mint),
+            ErrorCode::InvalidRiskParameters
+        );
+        // a position is only liquidatable below the threshold, so it has to sit
+        // above 100% and below the ratio new debt is opened at
+        require!(
+            self.liquidation_threshold > 100 && self.liquidation_threshold < self.min_health_factor,
+            ErrorCode::InvalidRiskParameters
+        );
+        require!(
+            self.min_health_factor <= MAX_MIN_HEALTH_FACTOR,
+            ErrorCode::InvalidRiskParameters
+        );
+        // the collateral left at the threshold must still cover debt plus bonus
+        require!(
+            self.liquidation_bonus <= MAX_LIQUIDATION_BONUS
+                && 100 + self.liquidation_bonus <= self.liquidation_threshold,
+            ErrorCode::InvalidRiskParameters
+        );
+        // every position with debt holds at least min_position_debt, so this
+        // bounds how many of them have to fit in the sorted positions list
+        require!(
+            (self.debt_ceiling as u128) <= MAX_SORTED_POSITIONS as u128 * min_position_debt as u128,
+            ErrorCode::InvalidRiskParameters
+        );
+        Ok(())
+    }
+
+    pub fn apply_update(&mut self, params: &CollateralTypeUpdateParams) {
+        if let Some(price_feed) = params.price_feed {
+            self.price_feed = price_feed;
+        }
+        if let Some(price_feed_id) = params.price_feed_id {
+            self.price_feed_id = price_feed_id;
+        }
+        if let Some(keeper_feed) = params.keeper_feed {
+            self.keeper_feed = keeper_feed;
+        }
+        if let Some(liquidation_threshold) = params.liquidation_threshold {
+            self.liquidation_threshold = liquidation_threshold;
+        }
+        if let Some(liquidation_bonus) = params.liquidation_bonus {
+            self.liquidation_bonus = liquidation_bonus;
+        }
+        if let Some(min_health_factor) = params.min_health_factor {
+            self.min_health_factor = min_health_factor;
+        }
+        if let Some(debt_ceiling) = params.debt_ceiling {
+            self.debt_ceiling = debt_ceiling;
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct CollateralTypeUpdateParams {
+    pub price_feed: Option<Pubkey>,
+    pub price_feed_id: Option<[u8; 32]>,
+    pub keeper_feed: Option<Pubkey>,
+    pub liquidation_threshold: Option<u64>,
+    pub liquidation_bonus: Option<u64>,
+    pub min_health_factor: Option<u64>,
+    pub debt_ceiling: Option<u64>,
+}
@@ -1,16 +1,477 @@
 use anchor_lang::prelude::*;
 
+use crate::{ConfigUpdateParams, ErrorCode, KeeperFeed, BPS_DENOMINATOR, MAX_CONFIG_UPDATE_DELAY, MAX_FEE_BPS, MAX_FULL_LIQUIDATION_HEALTH_FACTOR, MAX_STABILITY_FEE_RATE, MAX_TWAP_WINDOW, RATE_PRECISION};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum LiquidationMode {
+    FixedBonus,
+    Auction,
+}
+
+// which price a health factor is computed from. twap reads the collateral
+// type's PriceHistory over twap_window
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum PriceMode {
+    Spot,
+    Ema,
+    Twap,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct Config {
     pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub guardian: Pubkey,
     pub inrc_mint: Pubkey,
     pub usdc_mint: Pubkey,
+    pub usdc_decimals: u8,
+    // usdc/inr price account, and its pyth feed id when it is a PriceUpdateV2
+    // account. all zero for a legacy push account
+    pub usdc_price_feed: Pubkey,
+    pub usdc_price_feed_id: [u8; 32],
+    // keeper feed of usdc, the default pubkey when there is none
+    pub usdc_keeper_feed: Pubkey,
     pub treasury_authority: Pubkey,
-    pub liquidation_threshold: u64,
-    pub liquidation_bonus: u64,
-    pub min_health_factor: u64,
+    pub config_update_delay: i64,
+    pub paused_flags: u8,
+    pub stability_fee_rate: u128,
+    pub cumulative_rate_index: u128,
+    pub last_accrual_timestamp: i64,
+    pub surplus: u64,
+    pub deficit: u64,
+    // inrc in the fee vault that belongs to the insurance vault. it is
+    // swept there as usdc through the psm reserve
+    pub insurance_fees: u64,
+    pub mint_fee_bps: u16,
+    pub redeem_fee_bps: u16,
+    pub debt_ceiling: u64,
+    pub min_position_debt: u64,
+    pub close_factor_bps: u16,
+    pub full_liquidation_health_factor: u64,
+    pub liquidation_dust: u64,
+    pub liquidation_mode: LiquidationMode,
+    pub auction_start_discount_bps: u16,
+    pub auction_discount_bps_per_second: u16,
+    pub auction_max_discount_bps: u16,
+    pub auction_duration: i64,
+    pub insurance_share_bps: u16,
+    pub psm_fee_in_bps: u16,
+    pub psm_fee_out_bps: u16,
+    pub psm_capacity: u64,
+    pub redemption_fee_bps: u16,
+    pub max_price_confidence_bps: u16,
+    // how far a fresh fallback price may sit from the primary before both
+    // are distrusted
+    pub max_oracle_deviation_bps: u16,
+    // price used to decide fixed bonus liquidations and to start auctions
+    pub liquidation_price_mode: PriceMode,
+    pub auction_price_mode: PriceMode,
+    pub twap_window: i64,
     pub bump: u8,
     pub treasury_authority_bump: u8,
     pub mint_pda_bump: u8,
+    pub fee_vault_bump: u8,
+    pub insurance_vault_bump: u8,
+}
+
+impl Config {
+    pub fn validate_risk_params(&self) -> Result<()> {
+        require!(
+            self.usdc_keeper_feed == Pubkey::default() || self.usdc_keeper_feed == KeeperFeed::address(&self.usdc_mint),
+            ErrorCode::InvalidRiskParameters
+        );
+        require!(
+            (0..=MAX_CONFIG_UPDATE_DELAY).contains(&self.config_update_delay),
+            ErrorCode::InvalidRiskParameters
+        );
+        require!(
+            self.stability_fee_rate <= MAX_STABILITY_FEE_RATE,
+            ErrorCode::InvalidRiskParameters
+        );
+        require!(
+            self.mint_fee_bps <= MAX_FEE_BPS && self.redeem_fee_bps <= MAX_FEE_BPS,
+            ErrorCode::InvalidRiskParameters
+        );
+        require!(
+            self.min_position_debt <= self.debt_ceiling,
+            ErrorCode::InvalidRiskParameters
+        );
+        // a position just above min_position_debt can only be partly
+        // liquidated down to min_position_debt, so below that it has to be
+        // dust that is liquidated in full
+        require!(
+            self.liquidation_dust >= self.min_position_debt,
+            ErrorCode::InvalidRiskParameters
+        );
+        require!(
+            self.close_factor_bps > 0 && self.close_factor_bps as u64 <= BPS_DENOMINATOR,
+            ErrorCode::InvalidRiskParameters
+        );
+        require!(
+            self.full_liquidation_health_factor <= MAX_FULL_LIQUIDATION_HEALTH_FACTOR,
+            ErrorCode::InvalidRiskParameters
+        );
+        require!(
+            self.auction_start_discount_bps <= self.auction_max_discount_bps
+                && (self.auction_max_discount_bps as u64) < BPS_DENOMINATOR,
+            ErrorCode::InvalidRiskParameters
+        );
+        require!(
+            self.auction_duration > 0,
+            ErrorCode::InvalidRiskParameters
+        );
+        require!(
+            self.insurance_share_bps as u64 <= BPS_DENOMINATOR,
+            ErrorCode::InvalidRiskParameters
+        );
+        require!(
+            self.psm_fee_in_bps <= MAX_FEE_BPS && self.psm_fee_out_bps <= MAX_FEE_BPS,
+            ErrorCode::InvalidRiskParameters
+        );
+        require!(
+            self.redemption_fee_bps <= MAX_FEE_BPS,
+            ErrorCode::InvalidRiskParameters
+        );
+        require!(
+            (self.max_price_confidence_bps as u64) < BPS_DENOMINATOR,
+            ErrorCode::InvalidRiskParameters
+        );
+        require!(
+            self.max_oracle_deviation_bps > 0 && (self.max_oracle_deviation_bps as u64) < BPS_DENOMINATOR,
+            ErrorCode::InvalidRiskParameters
+        );
+        require!(
+            (1..=MAX_TWAP_WINDOW).contains(&self.twap_window),
+            ErrorCode::InvalidRiskParameters
+        );
+        Ok(())
+    }
+
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.paused_flags & flag != 0
+    }
+
+    pub fn apply_update(&mut self, params: &ConfigUpdateParams) {
+        if let Some(usdc_price_feed) = params.usdc_price_feed {
+            self.usdc_price_feed = usdc_price_feed;
+        }
+        if let Some(usdc_price_feed_id) = params.usdc_price_feed_id {
+            self.usdc_price_feed_id = usdc_price_feed_id;
+        }
+        if let Some(usdc_keeper_feed) = params.usdc_keeper_feed {
+            self.usdc_keeper_feed = usdc_keeper_feed;
+        }
+        if let Some(config_update_delay) = params.config_update_delay {
+            self.config_update_delay = config_update_delay;
+        }
+        if let Some(stability_fee_rate) = params.stability_fee_rate {
+            self.stability_fee_rate = stability_fee_rate;
+        }
+        if let Some(mint_fee_bps) = params.mint_fee_bps {
+            self.mint_fee_bps = mint_fee_bps;
+        }
+        if let Some(redeem_fee_bps) = params.redeem_fee_bps {
+            self.redeem_fee_bps = redeem_fee_bps;
+        }
+        if let Some(debt_ceiling) = params.debt_ceiling {
+            self.debt_ceiling = debt_ceiling;
+        }
+        if let Some(min_position_debt) = params.min_position_debt {
+            self.min_position_debt = min_position_debt;
+        }
+        if let Some(close_factor_bps) = params.close_factor_bps {
+            self.close_factor_bps = close_factor_bps;
+        }
+        if let Some(full_liquidation_health_factor) = params.full_liquidation_health_factor {
+            self.full_liquidation_health_factor = full_liquidation_health_factor;
+        }
+        if let Some(liquidation_dust) = params.liquidation_dust {
+            self.liquidation_dust = liquidation_dust;
+        }
+        if let Some(liquidation_mode) = params.liquidation_mode {
+            self.liquidation_mode = liquidation_mode;
+        }
+        if let Some(auction_start_discount_bps) = params.auction_start_discount_bps {
+            self.auction_start_discount_bps = auction_start_discount_bps;
+        }
+        if let Some(auction_discount_bps_per_second) = params.auction_discount_bps_per_second {
+            self.auction_discount_bps_per_second = auction_discount_bps_per_second;
+        }
+        if let Some(auction_max_discount_bps) = params.auction_max_discount_bps {
+            self.auction_max_discount_bps = auction_max_discount_bps;
+        }
+        if let Some(auction_duration) = params.auction_duration {
+            self.auction_duration = auction_duration;
+        }
+        if let Some(insurance_share_bps) = params.insurance_share_bps {
+            self.insurance_share_bps = insurance_share_bps;
+        }
+        if let Some(psm_fee_in_bps) = params.psm_fee_in_bps {
+            self.psm_fee_in_bps = psm_fee_in_bps;
+        }
+        if let Some(psm_fee_out_bps) = params.psm_fee_out_bps {
+            self.psm_fee_out_bps = psm_fee_out_bps;
+        }
+        if let Some(psm_capacity) = params.psm_capacity {
+            self.psm_capacity = psm_capacity;
+        }
+        if let Some(redemption_fee_bps) = params.redemption_fee_bps {
+            self.redemption_fee_bps = redemption_fee_bps;
+        }
+        if let Some(max_price_confidence_bps) = params.max_price_confidence_bps {
+            self.max_price_confidence_bps = max_price_confidence_bps;
+        }
+        if let Some(max_oracle_deviation_bps) = params.max_oracle_deviation_bps {
+            self.max_oracle_deviation_bps = max_oracle_deviation_bps;
+        }
+        if let Some(liquidation_price_mode) = params.liquidation_price_mode {
+            self.liquidation_price_mode = liquidation_price_mode;
+        }
+        if let Some(auction_price_mode) = params.auction_price_mode {
+            self.auction_price_mode = auction_price_mode;
+        }
+        if let Some(twap_window) = params.twap_window {
+            self.twap_window = twap_window;
+        }
+    }
+
+    // grows the rate index by the stability fee compounded over the time since
+    // the last accrual, and books the interest on all debt as surplus
+    pub fn accrue_stability_fee(&mut self, total_normalized_debt: u64, current_timestamp: i64) -> Result<()> {
+        let elapsed = current_timestamp.saturating_sub(self.last_accrual_timestamp);
+        if elapsed <= 0 {
+            return Ok(());
+        }
+
+        let rate_factor = rpow(
+            RATE_PRECISION.checked_add(self.stability_fee_rate).ok_or(ErrorCode::ArithmeticOverflow)?,
+            elapsed as u64,
+        )?;
+        let new_index = self.cumulative_rate_index
+            .checked_mul(rate_factor)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            / RATE_PRECISION;
+
+        let accrued_fees = (total_normalized_debt as u128)
+            .checked_mul(new_index - self.cumulative_rate_index)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            / RATE_PRECISION;
+
+        let accrued_fees = u64::try_from(accrued_fees).map_err(|_| ErrorCode::ArithmeticOverflow)?;
+
+        // recorded deficit is paid down before anything is added to surplus
+        let deficit_repaid = accrued_fees.min(self.deficit);
+        self.deficit -= deficit_repaid;
+        self.surplus = self.surplus
+            .checked_add(accrued_fees - deficit_repaid)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.cumulative_rate_index = new_index;
+        self.last_accrual_timestamp = current_timestamp;
+        Ok(())
+    }
+
+    // the insurance vault holds usdc, so only penalties paid in usdc feed it
+    pub fn insurance_share(&self, collateral_mint: &Pubkey, liquidation_penalty: u64) -> u64 {
+        if *collateral_mint != self.usdc_mint {
+            return 0;
+        }
+
+        (liquidation_penalty as u128 * self.insurance_share_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+
+    // sets aside insurance_share_bps of a fee paid into the fee vault
+    pub fn reserve_insurance_fee(&mut self, fee: u64) -> Result<()> {
+        let insurance_fee = (fee as u128 * self.insurance_share_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+        self.insurance_fees = self.insurance_fees
+            .checked_add(insurance_fee)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    // nets bad debt against surplus and returns what is left uncovered
+    pub fn cover_from_surplus(&mut self, bad_debt: u64) -> u64 {
+        let covered_by_surplus = bad_debt.min(self.surplus);
+        self.surplus -= covered_by_surplus;
+
+        bad_debt - covered_by_surplus
+    }
+
+    // spreads bad debt over all remaining debt by raising the rate index.
+    // only when no debt is left to carry it is it recorded as deficit. the
+    // bad debt must already be removed from total_normalized_debt
+    pub fn socialize_bad_debt(&mut self, total_normalized_debt: u64, uncovered: u64) -> Result<()> {
+        if uncovered == 0 {
+            return Ok(());
+        }
+
+        let total_debt = self.debt_from_normalized(total_normalized_debt)?;
+        if total_debt == 0 {
+            self.deficit = self.deficit.checked_add(uncovered).ok_or(ErrorCode::ArithmeticOverflow)?;
+            return Ok(());
+        }
+
+        self.cumulative_rate_index = self.cumulative_rate_index
+            .checked_mul(total_debt as u128 + uncovered as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .div_ceil(total_debt as u128);
+        msg!("Socialized {} inrc of bad debt", uncovered);
+
+        Ok(())
+    }
+
+    // debt is rounded up so positions never owe less than they borrowed
+    pub fn debt_from_normalized(&self, normalized_debt: u64) -> Result<u64> {
+        let debt = (normalized_debt as u128)
+            .checked_mul(self.cumulative_rate_index)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .div_ceil(RATE_PRECISION);
+
+        u64::try_from(debt).map_err(|_| error!(ErrorCode::ArithmeticOverflow))
+    }
+
+    // a position either has no debt or at least min_position_debt, so no
+    // dust is left that is not worth liquidating
+    pub fn check_position_debt(&self, normalized_debt: u64) -> Result<()> {
+        let debt = self.debt_from_normalized(normalized_debt)?;
+
+        if debt > 0 && debt < self.min_position_debt {
+            return err!(ErrorCode::BelowMinPositionDebt);
+        }
+
+        Ok(())
+    }
+
+    // a single liquidation may only repay close_factor_bps of the debt,
+    // unless the position is deeply underwater or its debt is dust
+    pub fn max_liquidation_amount(&self, inrc_debt: u64, collateral_ratio: u128) -> Result<u64> {
+        if inrc_debt <= self.liquidation_dust || collateral_ratio < self.full_liquidation_health_factor as u128 {
+            return Ok(inrc_debt);
+        }
+
+        let max_amount = (inrc_debt as u128)
+            .checked_mul(self.close_factor_bps as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            / BPS_DENOMINATOR as u128;
+
+        Ok(max_amount as u64)
+    }
+
+    // grows linearly from the start discount and stops at the max discount
+    pub fn auction_discount_bps(&self, started_at: i64, current_timestamp: i64) -> u64 {
+        let elapsed = current_timestamp.saturating_sub(started_at).max(0) as u64;
+
+        elapsed
+            .saturating_mul(self.auction_discount_bps_per_second as u64)
+            .saturating_add(self.auction_start_discount_bps as u64)
+            .min(self.auction_max_discount_bps as u64)
+    }
+
+    pub fn normalize_debt(&self, amount_inrc: u64) -> Result<u64> {
+        let normalized_debt = (amount_inrc as u128)
+            .checked_mul(RATE_PRECISION)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .div_ceil(self.cumulative_rate_index);
+
+        u64::try_from(normalized_debt).map_err(|_| error!(ErrorCode::ArithmeticOverflow))
+    }
+
+    // normalized debt cleared by repaying `amount_inrc`. repaying the full
+    // debt clears all of it so rounding never leaves dust behind
+    pub fn normalized_repayment(&self, normalized_debt: u64, amount_inrc: u64) -> Result<u64> {
+        let debt = self.debt_from_normalized(normalized_debt)?;
+
+        if amount_inrc > debt {
+            return err!(ErrorCode::LiquidationAmountTooHigh);
+        }
+
+        if amount_inrc == debt {
+            return Ok(normalized_debt);
+        }
+
+        let normalized_repayment = (amount_inrc as u128)
+            .checked_mul(RATE_PRECISION)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            / self.cumulative_rate_index;
+
+        Ok(normalized_repayment as u64)
+    }
+}
+
+// fixed point exponentiation by squaring, `base` scaled by RATE_PRECISION
+fn rpow(base: u128, exponent: u64) -> Result<u128> {
+    let mut result = RATE_PRECISION;
+    let mut base = base;
+    let mut exponent = exponent;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result.checked_mul(base).ok_or(ErrorCode::ArithmeticOverflow)? / RATE_PRECISION;
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            base = base.checked_mul(base).ok_or(ErrorCode::ArithmeticOverflow)? / RATE_PRECISION;
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rpow_compounds_per_second() {
+        assert_eq!(rpow(RATE_PRECISION * 2, 0).unwrap(), RATE_PRECISION);
+        assert_eq!(rpow(RATE_PRECISION * 2, 6).unwrap(), RATE_PRECISION * 64);
+        assert_eq!(rpow(RATE_PRECISION + RATE_PRECISION / 100, 2).unwrap(), RATE_PRECISION + RATE_PRECISION / 100 * 2 + RATE_PRECISION / 10_000);
+        assert!(rpow(RATE_PRECISION * 1_000, 10).is_err());
+    }
+
+    #[test]
+    fn accrue_stability_fee_pays_down_deficit_first() {
+        let mut config = Config::deserialize(&mut &[0u8; Config::INIT_SPACE][..]).unwrap();
+        config.cumulative_rate_index = RATE_PRECISION;
+        config.stability_fee_rate = RATE_PRECISION / 1_000;
+        config.last_accrual_timestamp = 100;
+        config.deficit = 400;
+
+        config.accrue_stability_fee(1_000_000, 101).unwrap();
+
+        assert_eq!(config.cumulative_rate_index, RATE_PRECISION + RATE_PRECISION / 1_000);
+        assert_eq!(config.deficit, 0);
+        assert_eq!(config.surplus, 600);
+        assert_eq!(config.last_accrual_timestamp, 101);
+        assert_eq!(config.debt_from_normalized(1_000_000).unwrap(), 1_001_000);
+
+        // a timestamp at or before the last accrual changes nothing
+        config.accrue_stability_fee(1_000_000, 90).unwrap();
+        assert_eq!(config.last_accrual_timestamp, 101);
+        assert_eq!(config.surplus, 600);
+    }
+
+    #[test]
+    fn normalized_repayment_clears_full_debt() {
+        let mut config = Config::deserialize(&mut &[0u8; Config::INIT_SPACE][..]).unwrap();
+        config.cumulative_rate_index = RATE_PRECISION * 3 / 2;
+
+        assert_eq!(config.debt_from_normalized(1_001).unwrap(), 1_502);
+        assert_eq!(config.normalized_repayment(1_001, 1_502).unwrap(), 1_001);
+        assert_eq!(config.normalized_repayment(1_001, 600).unwrap(), 400);
+        assert_eq!(config.normalized_repayment(1_001, 1_503).unwrap_err(), error!(ErrorCode::LiquidationAmountTooHigh));
+    }
+
+    #[test]
+    fn max_liquidation_amount_applies_close_factor() {
+        let mut config = Config::deserialize(&mut &[0u8; Config::INIT_SPACE][..]).unwrap();
+        config.close_factor_bps = 5_000;
+        config.full_liquidation_health_factor = 95;
+        config.liquidation_dust = 100;
+
+        assert_eq!(config.max_liquidation_amount(1_000, 120).unwrap(), 500);
+        // deeply underwater positions and dust can be liquidated in full
+        assert_eq!(config.max_liquidation_amount(1_000, 94).unwrap(), 1_000);
+        assert_eq!(config.max_liquidation_amount(100, 120).unwrap(), 100);
+    }
 }
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::SEED_KEEPER_FEED;
+
+// price pushed by a keeper, used as a fallback when the primary price of
+// `mint` is stale. stored in the same form as a pyth price so
+// it is scaled the same way
+#[account]
+#[derive(InitSpace)]
+pub struct KeeperFeed {
+    pub mint: Pubkey,
+    pub keeper: Pubkey,
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+    pub bump: u8,
+}
+
+impl KeeperFeed {
+    pub fn address(mint: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[SEED_KEEPER_FEED, mint.as_ref()], &crate::ID).0
+    }
+}
@@ -2,4 +2,43 @@ pub mod config;
 pub use config::*;
 
 pub mod user_collateral;
-pub use user_collateral::*;
\ No newline at end of file
+pub use user_collateral::*;
+
+pub mod pending_config_update;
+pub use pending_config_update::*;
+
+pub mod collateral_type;
+pub use collateral_type::*;
+
+pub mod pending_collateral_type_update;
+pub use pending_collateral_type_update::*;
+
+pub mod portfolio;
+pub use portfolio::*;
+
+pub mod protocol_state;
+pub use protocol_state::*;
+
+pub mod auction;
+pub use auction::*;
+
+pub mod stability_pool;
+pub use stability_pool::*;
+
+pub mod stability_pool_epoch;
+pub use stability_pool_epoch::*;
+
+pub mod stability_deposit;
+pub use stability_deposit::*;
+
+pub mod psm;
+pub use psm::*;
+
+pub mod sorted_positions;
+pub use sorted_positions::*;
+
+pub mod keeper_feed;
+pub use keeper_feed::*;
+
+pub mod price_history;
+pub use price_history::*;
\ No newline at end of file
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+use crate::CollateralTypeUpdateParams;
+
+#[account]
+#[derive(InitSpace)]
+pub struct PendingCollateralTypeUpdate {
+    pub collateral_mint: Pubkey,
+    pub params: CollateralTypeUpdateParams,
+    pub proposed_at: i64,
+    pub executable_at: i64,
+    pub bump: u8,
+}
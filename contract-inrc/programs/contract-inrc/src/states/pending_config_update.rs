@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{LiquidationMode, PriceMode};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct ConfigUpdateParams {
+    pub usdc_price_feed: Option<Pubkey>,
+    pub usdc_price_feed_id: Option<[u8; 32]>,
+    pub usdc_keeper_feed: Option<Pubkey>,
+    pub config_update_delay: Option<i64>,
+    pub stability_fee_rate: Option<u128>,
+    pub mint_fee_bps: Option<u16>,
+    pub redeem_fee_bps: Option<u16>,
+    pub debt_ceiling: Option<u64>,
+    pub min_position_debt: Option<u64>,
+    pub close_factor_bps: Option<u16>,
+    pub full_liquidation_health_factor: Option<u64>,
+    pub liquidation_dust: Option<u64>,
+    pub liquidation_mode: Option<LiquidationMode>,
+    pub auction_start_discount_bps: Option<u16>,
+    pub auction_discount_bps_per_second: Option<u16>,
+    pub auction_max_discount_bps: Option<u16>,
+    pub auction_duration: Option<i64>,
+    pub insurance_share_bps: Option<u16>,
+    pub psm_fee_in_bps: Option<u16>,
+    pub psm_fee_out_bps: Option<u16>,
+    pub psm_capacity: Option<u64>,
+    pub redemption_fee_bps: Option<u16>,
+    pub max_price_confidence_bps: Option<u16>,
+    pub max_oracle_deviation_bps: Option<u16>,
+    pub liquidation_price_mode: Option<PriceMode>,
+    pub auction_price_mode: Option<PriceMode>,
+    pub twap_window: Option<i64>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PendingConfigUpdate {
+    pub params: ConfigUpdateParams,
+    pub proposed_at: i64,
+    pub executable_at: i64,
+    pub bump: u8,
+}
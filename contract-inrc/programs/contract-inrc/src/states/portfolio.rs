@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::MAX_PORTFOLIO_ASSETS;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct PortfolioBalance {
+    pub collateral_mint: Pubkey,
+    pub amount: u64,
+    // the part of the portfolio debt charged to this collateral type's
+    // total_normalized_debt
+    pub normalized_debt: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Portfolio {
+    pub owner: Pubkey,
+    #[max_len(MAX_PORTFOLIO_ASSETS)]
+    pub balances: Vec<PortfolioBalance>,
+    pub normalized_debt: u64,
+    pub bump: u8,
+}
+
+impl Portfolio {
+    pub fn balance_of(&self, collateral_mint: &Pubkey) -> u64 {
+        self.balances
+            .iter()
+            .find(|balance| balance.collateral_mint == *collateral_mint)
+            .map_or(0, |balance| balance.amount)
+    }
+
+    // an empty balance is kept until no debt is charged to it any more
+    pub fn prune_balances(&mut self) {
+        self.balances.retain(|balance| balance.amount > 0 || balance.normalized_debt > 0);
+    }
+}
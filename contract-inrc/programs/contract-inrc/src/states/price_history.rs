@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+
+use crate::{ErrorCode, MAX_AGE, MAX_PRICE_OBSERVATIONS, PRICE_OBSERVATION_INTERVAL};
+
+// `cumulative_price` is the sum of price * seconds up to `timestamp`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct PriceObservation {
+    pub timestamp: i64,
+    pub price: u128,
+    pub cumulative_price: u128,
+}
+
+impl PriceObservation {
+    // each observed price holds until the next observation
+    fn cumulative_price_at(&self, timestamp: i64) -> Result<u128> {
+        let cumulative_price = self.price
+            .checked_mul(timestamp.saturating_sub(self.timestamp) as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_add(self.cumulative_price)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        Ok(cumulative_price)
+    }
+}
+
+// ring buffer of spot prices of one collateral type, recorded by keepers at
+// most once per PRICE_OBSERVATION_INTERVAL. `head` is the newest observation
+#[account]
+#[derive(InitSpace)]
+pub struct PriceHistory {
+    pub mint: Pubkey,
+    pub head: u16,
+    pub len: u16,
+    pub observations: [PriceObservation; MAX_PRICE_OBSERVATIONS],
+    pub bump: u8,
+}
+
+impl PriceHistory {
+    pub fn record(&mut self, price: u128, current_timestamp: i64) -> Result<()> {
+        let mut cumulative_price = 0;
+
+        if self.len > 0 {
+            let newest = self.observations[self.head as usize];
+            if current_timestamp < newest.timestamp.saturating_add(PRICE_OBSERVATION_INTERVAL) {
+                return err!(ErrorCode::PriceObservationTooSoon);
+            }
+
+            cumulative_price = newest.cumulative_price_at(current_timestamp)?;
+            self.head = ((self.head as usize + 1) % MAX_PRICE_OBSERVATIONS) as u16;
+        }
+
+        self.observations[self.head as usize] = PriceObservation {
+            timestamp: current_timestamp,
+            price,
+            cumulative_price,
+        };
+        self.len = (self.len + 1).min(MAX_PRICE_OBSERVATIONS as u16);
+
+        Ok(())
+    }
+
+    // time weighted average over the `window` seconds up to now. the history
+    // has to reach back past the window and must not have stopped updating
+    pub fn twap(&self, current_timestamp: i64, window: i64) -> Result<u128> {
+        if self.len == 0 {
+            return err!(ErrorCode::PriceHistoryUnavailable);
+        }
+
+        let newest = self.observations[self.head as usize];
+        if current_timestamp.saturating_sub(newest.timestamp) > PRICE_OBSERVATION_INTERVAL + MAX_AGE as i64 {
+            return err!(ErrorCode::PriceHistoryUnavailable);
+        }
+
+        let window_start = current_timestamp.saturating_sub(window);
+        let start = (0..self.len as usize)
+            .map(|offset| self.observations[(self.head as usize + MAX_PRICE_OBSERVATIONS - offset) % MAX_PRICE_OBSERVATIONS])
+            .find(|observation| observation.timestamp <= window_start)
+            .ok_or(ErrorCode::PriceHistoryUnavailable)?;
+
+        let cumulative_price = newest.cumulative_price_at(current_timestamp)?
+            .checked_sub(start.cumulative_price_at(window_start)?)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        Ok(cumulative_price / window as u128)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn twap_weights_prices_by_time() {
+        let mut price_history = PriceHistory {
+            mint: Pubkey::default(),
+            head: 0,
+            len: 0,
+            observations: [PriceObservation::default(); MAX_PRICE_OBSERVATIONS],
+            bump: 0,
+        };
+        assert_eq!(price_history.twap(1_000, 60).unwrap_err(), error!(ErrorCode::PriceHistoryUnavailable));
+
+        price_history.record(100, 1_000).unwrap();
+        price_history.record(200, 1_060).unwrap();
+
+        assert_eq!(price_history.twap(1_120, 120).unwrap(), 150);
+        // a window starting between observations reads the older price for
+        // the part before the newer one
+        assert_eq!(price_history.twap(1_120, 90).unwrap(), 166);
+        // the history has to reach back past the window
+        assert_eq!(price_history.twap(1_120, 180).unwrap_err(), error!(ErrorCode::PriceHistoryUnavailable));
+
+        let stale_at = 1_060 + PRICE_OBSERVATION_INTERVAL + MAX_AGE as i64 + 1;
+        assert_eq!(price_history.twap(stale_at, 60).unwrap_err(), error!(ErrorCode::PriceHistoryUnavailable));
+    }
+
+    #[test]
+    fn record_wraps_around_and_rate_limits() {
+        let mut price_history = PriceHistory {
+            mint: Pubkey::default(),
+            head: 0,
+            len: 0,
+            observations: [PriceObservation::default(); MAX_PRICE_OBSERVATIONS],
+            bump: 0,
+        };
+        price_history.record(100, 1_000).unwrap();
+        assert_eq!(price_history.record(100, 1_000 + PRICE_OBSERVATION_INTERVAL - 1).unwrap_err(), error!(ErrorCode::PriceObservationTooSoon));
+
+        for index in 1..=MAX_PRICE_OBSERVATIONS as i64 {
+            price_history.record(100, 1_000 + index * PRICE_OBSERVATION_INTERVAL).unwrap();
+        }
+
+        assert_eq!(price_history.len as usize, MAX_PRICE_OBSERVATIONS);
+        assert_eq!(price_history.head, 0);
+        assert_eq!(price_history.twap(1_000 + MAX_PRICE_OBSERVATIONS as i64 * PRICE_OBSERVATION_INTERVAL, 600).unwrap(), 100);
+    }
+}
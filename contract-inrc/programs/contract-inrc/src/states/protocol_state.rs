@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::{ErrorCode, MAX_COLLATERAL_TYPES};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct CollateralTotal {
+    pub collateral_mint: Pubkey,
+    pub decimals: u8,
+    pub amount: u64,
+    // last price recorded by update_collateral_price, and when
+    pub price: u128,
+    pub priced_at: i64,
+}
+
+// collateral held across every position and portfolio, one entry per
+// registered collateral type, and the normalized debt owed against it
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolState {
+    #[max_len(MAX_COLLATERAL_TYPES)]
+    pub collateral_totals: Vec<CollateralTotal>,
+    pub total_normalized_debt: u64,
+    pub bump: u8,
+}
+
+impl ProtocolState {
+    pub fn add_collateral_type(&mut self, collateral_mint: Pubkey, decimals: u8) -> Result<()> {
+        if self.collateral_totals.len() >= MAX_COLLATERAL_TYPES as usize {
+            return err!(ErrorCode::TooManyCollateralTypes);
+        }
+
+        self.collateral_totals.push(CollateralTotal {
+            collateral_mint,
+            decimals,
+            amount: 0,
+            price: 0,
+            priced_at: 0,
+        });
+
+        Ok(())
+    }
+
+    pub fn credit_collateral(&mut self, collateral_mint: &Pubkey, amount: u64) -> Result<()> {
+        let total = self.total_mut(collateral_mint)?;
+        total.amount = total.amount.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    pub fn debit_collateral(&mut self, collateral_mint: &Pubkey, amount: u64) -> Result<()> {
+        let total = self.total_mut(collateral_mint)?;
+        total.amount = total.amount.checked_sub(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_collateral_price(&mut self, collateral_mint: &Pubkey, price: u128, current_timestamp: i64) -> Result<()> {
+        let total = self.total_mut(collateral_mint)?;
+        total.price = price;
+        total.priced_at = current_timestamp;
+        Ok(())
+    }
+
+    fn total_mut(&mut self, collateral_mint: &Pubkey) -> Result<&mut CollateralTotal> {
+        self.collateral_totals
+            .iter_mut()
+            .find(|total| total.collateral_mint == *collateral_mint)
+            .ok_or(error!(ErrorCode::CollateralTypeMismatch))
+    }
+}
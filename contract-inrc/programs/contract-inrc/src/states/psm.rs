@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+// peg stability module. usdc swapped in sits in the psm reserve and
+// `minted_inrc` is the inrc it has minted and not yet taken back
+#[account]
+#[derive(InitSpace)]
+pub struct Psm {
+    pub minted_inrc: u64,
+    pub bump: u8,
+    pub reserve_bump: u8,
+}
@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+
+use crate::{ErrorCode, UserCollateral, MAX_SORTED_POSITIONS, MINT_DECIMAL, NICR_DECIMALS};
+
+#[zero_copy]
+pub struct SortedPosition {
+    pub owner: Pubkey,
+    pub position_id: u64,
+    pub nicr: u64,
+}
+
+// positions of one collateral type that carry debt, lowest health factor
+// first. they are keyed by collateral per unit of normalized debt, which
+// orders them the same way at any price or rate index. every position with
+// debt is listed, a debt ceiling of at most MAX_SORTED_POSITIONS times
+// min_position_debt keeps the list from filling up
+#[account(zero_copy)]
+pub struct SortedPositions {
+    pub collateral_mint: Pubkey,
+    pub len: u64,
+    pub entries: [SortedPosition; MAX_SORTED_POSITIONS],
+}
+
+impl SortedPositions {
+    // None for a position without debt, which is not listed
+    pub fn sort_key(position: &UserCollateral, collateral_decimals: u8) -> Option<u64> {
+        if position.normalized_debt == 0 {
+            return None;
+        }
+
+        let nicr = (position.collateral_amount as u128)
+            .saturating_mul(10u128.pow(NICR_DECIMALS as u32 + MINT_DECIMAL as u32))
+            / 10u128.pow(collateral_decimals as u32)
+            / position.normalized_debt as u128;
+
+        Some(nicr.min(u64::MAX as u128) as u64)
+    }
+
+    pub fn get(&self, index: usize) -> Option<SortedPosition> {
+        if index >= self.len as usize {
+            return None;
+        }
+
+        Some(self.entries[index])
+    }
+
+    // moves the position from `sort_key_before` to where it belongs now
+    pub fn update(&mut self, position: &UserCollateral, sort_key_before: Option<u64>, collateral_decimals: u8) -> Result<()> {
+        if let Some(sort_key) = sort_key_before {
+            self.remove(position.depositor, position.position_id, sort_key);
+        }
+
+        if let Some(sort_key) = Self::sort_key(position, collateral_decimals) {
+            self.insert(position.depositor, position.position_id, sort_key)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn remove(&mut self, owner: Pubkey, position_id: u64, sort_key: u64) {
+        let len = self.len as usize;
+        let start = self.entries[..len].partition_point(|entry| entry.nicr < sort_key);
+
+        if let Some(offset) = self.entries[start..len]
+            .iter()
+            .take_while(|entry| entry.nicr == sort_key)
+            .position(|entry| entry.owner == owner && entry.position_id == position_id)
+        {
+            let index = start + offset;
+            self.entries.copy_within(index + 1..len, index);
+            self.len -= 1;
+        }
+    }
+
+    fn insert(&mut self, owner: Pubkey, position_id: u64, sort_key: u64) -> Result<()> {
+        let len = self.len as usize;
+
+        if len >= MAX_SORTED_POSITIONS {
+            return err!(ErrorCode::SortedPositionsFull);
+        }
+
+        let index = self.entries[..len].partition_point(|entry| entry.nicr <= sort_key);
+        self.entries.copy_within(index..len, index + 1);
+        self.entries[index] = SortedPosition {
+            owner,
+            position_id,
+            nicr: sort_key,
+        };
+        self.len = len as u64 + 1;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_keeps_lowest_nicr_first() {
+        let mut sorted_positions: Box<SortedPositions> = Box::new(bytemuck::Zeroable::zeroed());
+        let owner = Pubkey::new_unique();
+
+        sorted_positions.insert(owner, 1, 30).unwrap();
+        sorted_positions.insert(owner, 2, 10).unwrap();
+        sorted_positions.insert(owner, 3, 20).unwrap();
+        sorted_positions.insert(owner, 4, 20).unwrap();
+
+        let nicrs: Vec<u64> = (0..4).map(|index| sorted_positions.get(index).unwrap().nicr).collect();
+        assert_eq!(nicrs, vec![10, 20, 20, 30]);
+        // equal keys keep insertion order
+        assert_eq!(sorted_positions.get(1).unwrap().position_id, 3);
+        assert_eq!(sorted_positions.get(2).unwrap().position_id, 4);
+        assert!(sorted_positions.get(4).is_none());
+
+        sorted_positions.remove(owner, 3, 20);
+        assert_eq!(sorted_positions.len, 3);
+        assert_eq!(sorted_positions.get(1).unwrap().position_id, 4);
+
+        // a position under another key is left alone
+        sorted_positions.remove(owner, 1, 20);
+        assert_eq!(sorted_positions.len, 3);
+    }
+
+    #[test]
+    fn update_unlists_a_position_without_debt() {
+        let mut sorted_positions: Box<SortedPositions> = Box::new(bytemuck::Zeroable::zeroed());
+        let mut position = UserCollateral {
+            depositor: Pubkey::new_unique(),
+            position_id: 1,
+            collateral_mint: Pubkey::new_unique(),
+            collateral_amount: 1_000_000_000,
+            normalized_debt: 1_000_000,
+            bump: 0,
+        };
+
+        sorted_positions.update(&position, None, 9).unwrap();
+        assert_eq!(sorted_positions.len, 1);
+
+        let sort_key_before = SortedPositions::sort_key(&position, 9);
+        position.normalized_debt = 0;
+        sorted_positions.update(&position, sort_key_before, 9).unwrap();
+        assert_eq!(sorted_positions.len, 0);
+    }
+
+    #[test]
+    fn insert_into_a_full_list_fails() {
+        let mut sorted_positions: Box<SortedPositions> = Box::new(bytemuck::Zeroable::zeroed());
+        let owner = Pubkey::new_unique();
+
+        for position_id in 0..MAX_SORTED_POSITIONS as u64 {
+            sorted_positions.insert(owner, position_id, 100 + position_id).unwrap();
+        }
+
+        assert_eq!(sorted_positions.insert(owner, u64::MAX, 1).unwrap_err(), error!(ErrorCode::SortedPositionsFull));
+        assert_eq!(sorted_positions.len as usize, MAX_SORTED_POSITIONS);
+        assert_eq!(sorted_positions.get(0).unwrap().nicr, 100);
+    }
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::{CollateralSum, ErrorCode, MAX_COLLATERAL_TYPES};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct CollateralGain {
+    pub collateral_mint: Pubkey,
+    pub amount: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StabilityDeposit {
+    pub owner: Pubkey,
+    pub initial_amount: u64,
+    pub product_snapshot: u128,
+    pub epoch: u64,
+    pub scale: u64,
+    #[max_len(MAX_COLLATERAL_TYPES)]
+    pub sum_snapshots: Vec<CollateralSum>,
+    // collateral gains settled but not claimed yet
+    #[max_len(MAX_COLLATERAL_TYPES)]
+    pub pending_gains: Vec<CollateralGain>,
+    pub bump: u8,
+}
+
+impl StabilityDeposit {
+    pub fn snapshot_of(&self, collateral_mint: &Pubkey) -> u128 {
+        self.sum_snapshots
+            .iter()
+            .find(|snapshot| snapshot.collateral_mint == *collateral_mint)
+            .map_or(0, |snapshot| snapshot.sum)
+    }
+
+    pub fn credit_gain(&mut self, collateral_mint: Pubkey, amount: u64) -> Result<()> {
+        match self.pending_gains.iter_mut().find(|gain| gain.collateral_mint == collateral_mint) {
+            Some(gain) => {
+                gain.amount = gain.amount.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+            }
+            None => {
+                if self.pending_gains.len() >= MAX_COLLATERAL_TYPES as usize {
+                    return err!(ErrorCode::TooManyCollateralTypes);
+                }
+                self.pending_gains.push(CollateralGain {
+                    collateral_mint,
+                    amount,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // removes and returns the pending gain in `collateral_mint`
+    pub fn take_gain(&mut self, collateral_mint: &Pubkey) -> u64 {
+        match self.pending_gains.iter().position(|gain| gain.collateral_mint == *collateral_mint) {
+            Some(index) => self.pending_gains.remove(index).amount,
+            None => 0,
+        }
+    }
+}
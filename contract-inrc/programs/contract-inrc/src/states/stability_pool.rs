@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+
+use crate::{ErrorCode, StabilityDeposit, StabilityPoolEpoch, POOL_PRECISION, POOL_SCALE_FACTOR};
+
+// inrc deposited to absorb liquidations. `product` tracks how much of a
+// deposit made at product 1 is left, and resets when an epoch ends. when it
+// drops below POOL_SCALE_FACTOR it is scaled back up and `scale` goes up by
+// one, so it never rounds down to zero
+#[account]
+#[derive(InitSpace)]
+pub struct StabilityPool {
+    pub total_deposits: u64,
+    pub product: u128,
+    pub epoch: u64,
+    pub scale: u64,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl StabilityPool {
+    // a deposit snapshotted more than one scale ago has less than
+    // 1 / POOL_SCALE_FACTOR of it left, which rounds to nothing
+    pub fn compounded_deposit(&self, deposit: &StabilityDeposit) -> u64 {
+        if deposit.epoch != self.epoch || deposit.product_snapshot == 0 {
+            return 0;
+        }
+
+        let compounded_deposit = deposit.initial_amount as u128 * self.product / deposit.product_snapshot;
+
+        match self.scale.saturating_sub(deposit.scale) {
+            0 => compounded_deposit as u64,
+            1 => (compounded_deposit / POOL_SCALE_FACTOR) as u64,
+            _ => 0,
+        }
+    }
+
+    // burns `debt` of pooled inrc against `collateral` handed to depositors,
+    // pro rata. emptying the pool ends the epoch. `epoch_sums` has to be the
+    // sums of the current epoch and scale
+    pub fn offset(&mut self, epoch_sums: &mut StabilityPoolEpoch, debt: u64, collateral_mint: Pubkey, collateral: u64) -> Result<()> {
+        if debt == 0 || debt > self.total_deposits {
+            return err!(ErrorCode::InsufficientStabilityPoolDeposits);
+        }
+
+        let sum_increase = (collateral as u128)
+            .checked_mul(self.product)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            / self.total_deposits as u128;
+        epoch_sums.add_to_sum(collateral_mint, sum_increase)?;
+
+        if debt == self.total_deposits {
+            self.epoch = self.epoch.checked_add(1).ok_or(ErrorCode::ArithmeticOverflow)?;
+            self.scale = 0;
+            self.product = POOL_PRECISION;
+            self.total_deposits = 0;
+            return Ok(());
+        }
+
+        // product * remaining / total_deposits by long division, so each
+        // scale step keeps the digits the division would otherwise drop
+        let total_deposits = self.total_deposits as u128;
+        let numerator = self.product
+            .checked_mul((self.total_deposits - debt) as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        let mut new_product = numerator / total_deposits;
+        let mut remainder = numerator % total_deposits;
+
+        while new_product < POOL_SCALE_FACTOR {
+            remainder *= POOL_SCALE_FACTOR;
+            new_product = new_product * POOL_SCALE_FACTOR + remainder / total_deposits;
+            remainder %= total_deposits;
+            self.scale = self.scale.checked_add(1).ok_or(ErrorCode::ArithmeticOverflow)?;
+        }
+
+        self.product = new_product;
+        self.total_deposits -= debt;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offset_shares_debt_and_collateral_pro_rata() {
+        let mut stability_pool = StabilityPool { total_deposits: 1_000, product: POOL_PRECISION, epoch: 0, scale: 0, bump: 0, vault_bump: 0 };
+        let mut epoch_sums = StabilityPoolEpoch { epoch: 0, scale: 0, sums: Vec::new(), bump: 0 };
+        let deposit = StabilityDeposit {
+            owner: Pubkey::default(),
+            initial_amount: 500,
+            product_snapshot: POOL_PRECISION,
+            epoch: 0,
+            scale: 0,
+            sum_snapshots: Vec::new(),
+            pending_gains: Vec::new(),
+            bump: 0,
+        };
+        let collateral_mint = Pubkey::new_unique();
+
+        stability_pool.offset(&mut epoch_sums, 400, collateral_mint, 50).unwrap();
+
+        assert_eq!(stability_pool.total_deposits, 600);
+        assert_eq!(stability_pool.compounded_deposit(&deposit), 300);
+        // 50 collateral over 1000 inrc, per unit of deposit
+        assert_eq!(epoch_sums.sum_of(&collateral_mint), POOL_PRECISION / 20);
+
+        assert_eq!(
+            stability_pool.offset(&mut epoch_sums, 601, collateral_mint, 10).unwrap_err(),
+            error!(ErrorCode::InsufficientStabilityPoolDeposits)
+        );
+    }
+
+    #[test]
+    fn offset_scales_the_product_instead_of_reaching_zero() {
+        let mut stability_pool = StabilityPool { total_deposits: 1_000_000_000_000, product: POOL_PRECISION, epoch: 0, scale: 0, bump: 0, vault_bump: 0 };
+        let mut epoch_sums = StabilityPoolEpoch { epoch: 0, scale: 0, sums: Vec::new(), bump: 0 };
+        let mut deposit = StabilityDeposit {
+            owner: Pubkey::default(),
+            initial_amount: 1_000_000_000_000,
+            product_snapshot: POOL_PRECISION,
+            epoch: 0,
+            scale: 0,
+            sum_snapshots: Vec::new(),
+            pending_gains: Vec::new(),
+            bump: 0,
+        };
+
+        stability_pool.offset(&mut epoch_sums, 999_999_999_999, Pubkey::new_unique(), 1).unwrap();
+
+        assert_eq!(stability_pool.scale, 1);
+        assert!(stability_pool.product >= POOL_SCALE_FACTOR);
+        assert_eq!(stability_pool.compounded_deposit(&deposit), 1);
+
+        // a deposit made after the scale change is not scaled down
+        deposit.initial_amount = 1_000;
+        deposit.product_snapshot = stability_pool.product;
+        deposit.scale = stability_pool.scale;
+        assert_eq!(stability_pool.compounded_deposit(&deposit), 1_000);
+    }
+
+    #[test]
+    fn offset_of_all_deposits_ends_the_epoch() {
+        let mut stability_pool = StabilityPool { total_deposits: 1_000, product: POOL_PRECISION / 2, epoch: 0, scale: 1, bump: 0, vault_bump: 0 };
+        let mut epoch_sums = StabilityPoolEpoch { epoch: 0, scale: 1, sums: Vec::new(), bump: 0 };
+
+        stability_pool.offset(&mut epoch_sums, 1_000, Pubkey::new_unique(), 10).unwrap();
+
+        assert_eq!(stability_pool.epoch, 1);
+        assert_eq!(stability_pool.scale, 0);
+        assert_eq!(stability_pool.product, POOL_PRECISION);
+        assert_eq!(stability_pool.total_deposits, 0);
+    }
+}
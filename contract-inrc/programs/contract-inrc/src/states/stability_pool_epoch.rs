@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{ErrorCode, MAX_COLLATERAL_TYPES};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct CollateralSum {
+    pub collateral_mint: Pubkey,
+    pub sum: u128,
+}
+
+// running collateral gain per unit of deposit for one epoch and scale of
+// the pool, kept after they end so depositors can still settle against it
+#[account]
+#[derive(InitSpace)]
+pub struct StabilityPoolEpoch {
+    pub epoch: u64,
+    pub scale: u64,
+    #[max_len(MAX_COLLATERAL_TYPES)]
+    pub sums: Vec<CollateralSum>,
+    pub bump: u8,
+}
+
+impl StabilityPoolEpoch {
+    pub fn sum_of(&self, collateral_mint: &Pubkey) -> u128 {
+        self.sums
+            .iter()
+            .find(|sum| sum.collateral_mint == *collateral_mint)
+            .map_or(0, |sum| sum.sum)
+    }
+
+    pub fn add_to_sum(&mut self, collateral_mint: Pubkey, amount: u128) -> Result<()> {
+        match self.sums.iter_mut().find(|sum| sum.collateral_mint == collateral_mint) {
+            Some(sum) => {
+                sum.sum = sum.sum.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+            }
+            None => {
+                if self.sums.len() >= MAX_COLLATERAL_TYPES as usize {
+                    return err!(ErrorCode::TooManyCollateralTypes);
+                }
+                self.sums.push(CollateralSum {
+                    collateral_mint,
+                    sum: amount,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
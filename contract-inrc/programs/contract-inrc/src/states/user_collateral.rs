@@ -4,7 +4,9 @@ use anchor_lang::prelude::*;
 #[derive(InitSpace)]
 pub struct UserCollateral {
     pub depositor: Pubkey,
-    pub usdc_deposit: u64, 
-    pub inrc_minted: u64,
+    pub position_id: u64,
+    pub collateral_mint: Pubkey,
+    pub collateral_amount: u64, 
+    pub normalized_debt: u64,
     pub bump: u8,
-}
\ No newline at end of file
+}
@@ -1,16 +1,287 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program } from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
 import { ContractNew } from "../target/types/contract_new";
 
+// the sorted positions account is too large to be created by the program
+const SORTED_POSITIONS_SPACE = 8 + 32 + 8 + 2048 * (32 + 8 + 8);
+const RATE_PRECISION = new BN("1000000000000000000");
+
 describe("contract-new", () => {
-  // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
 
   const program = anchor.workspace.contractNew as Program<ContractNew>;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const authority = provider.wallet.publicKey;
+
+  // nothing is deployed at the price feeds on localnet, so the primary
+  // price is never available
+  const usdcPriceFeed = Keypair.generate().publicKey;
+  const collateralPriceFeed = Keypair.generate().publicKey;
+
+  let usdcMint: PublicKey;
+  let collateralMint: PublicKey;
+  let collateralKeeperFeed: PublicKey;
+  const sortedPositions = Keypair.generate();
+  const positionId = new BN(1);
+
+  const pda = (...seeds: (Buffer | Uint8Array)[]) =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const initConfigParams = () => ({
+    usdcPriceFeed,
+    usdcPriceFeedId: new Array(32).fill(0),
+    usdcKeeperFeed: PublicKey.default,
+    configUpdateDelay: new BN(0),
+    stabilityFeeRate: new BN(0),
+    mintFeeBps: 50,
+    redeemFeeBps: 0,
+    debtCeiling: new BN("1000000000000000"),
+    minPositionDebt: new BN(1_000_000),
+    closeFactorBps: 5_000,
+    fullLiquidationHealthFactor: new BN(95),
+    liquidationDust: new BN(1_000_000),
+    liquidationMode: { fixedBonus: {} },
+    auctionStartDiscountBps: 0,
+    auctionDiscountBpsPerSecond: 10,
+    auctionMaxDiscountBps: 2_000,
+    auctionDuration: new BN(3_600),
+    insuranceShareBps: 5_000,
+    psmFeeInBps: 10,
+    psmFeeOutBps: 10,
+    psmCapacity: new BN("1000000000000"),
+    redemptionFeeBps: 50,
+    maxPriceConfidenceBps: 200,
+    maxOracleDeviationBps: 500,
+    liquidationPriceMode: { spot: {} },
+    auctionPriceMode: { spot: {} },
+    twapWindow: new BN(600),
+  });
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      assert.fail(`expected ${code}`);
+    } catch (error) {
+      assert.equal(error.error?.errorCode?.code, code);
+    }
+  };
+
+  before(async () => {
+    usdcMint = await createMint(provider.connection, payer, authority, null, 6);
+    collateralMint = await createMint(provider.connection, payer, authority, null, 9);
+    collateralKeeperFeed = pda(Buffer.from("keeper_feed"), collateralMint.toBuffer());
+
+    const userCollateralAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      collateralMint,
+      authority
+    );
+    await mintTo(provider.connection, payer, collateralMint, userCollateralAccount.address, authority, 10_000_000_000);
+  });
+
+  it("initializes the config", async () => {
+    await program.methods
+      .initializeConfig(initConfigParams())
+      .accounts({ signer: authority, usdcMint })
+      .rpc();
+
+    const config = await program.account.config.fetch(pda(Buffer.from("config")));
+    assert.ok(config.authority.equals(authority));
+    assert.ok(config.usdcMint.equals(usdcMint));
+    assert.ok(config.cumulativeRateIndex.eq(RATE_PRECISION));
+    assert.equal(config.maxOracleDeviationBps, 500);
+  });
+
+  it("rejects a config update with invalid risk parameters", async () => {
+    await expectError(
+      program.methods
+        .proposeConfigUpdate({ ...emptyConfigUpdate(), liquidationDust: new BN(1) })
+        .accounts({ authority })
+        .rpc(),
+      "InvalidRiskParameters"
+    );
+  });
+
+  it("registers a collateral type with a keeper feed", async () => {
+    await program.methods
+      .initializeKeeperFeed(authority)
+      .accounts({ authority, mint: collateralMint })
+      .rpc();
+
+    await program.methods
+      .updateKeeperFeed(new BN(500_000), new BN(100), -2)
+      .accounts({ keeper: authority, keeperFeed: collateralKeeperFeed })
+      .rpc();
+
+    const lamports = await provider.connection.getMinimumBalanceForRentExemption(SORTED_POSITIONS_SPACE);
+    await program.methods
+      .registerCollateralType({
+        priceFeed: collateralPriceFeed,
+        priceFeedId: new Array(32).fill(0),
+        keeperFeed: collateralKeeperFeed,
+        liquidationThreshold: new BN(120),
+        liquidationBonus: new BN(10),
+        minHealthFactor: new BN(150),
+        // at most 2048 positions of minPositionDebt fit in the sorted list
+        debtCeiling: new BN(2_000_000_000),
+      })
+      .accounts({
+        authority,
+        collateralMint,
+        sortedPositions: sortedPositions.publicKey,
+      })
+      .preInstructions([
+        SystemProgram.createAccount({
+          fromPubkey: authority,
+          newAccountPubkey: sortedPositions.publicKey,
+          space: SORTED_POSITIONS_SPACE,
+          lamports,
+          programId: program.programId,
+        }),
+      ])
+      .signers([sortedPositions])
+      .rpc();
 
-  it("Is initialized!", async () => {
-    // Add your test here.
-    const tx = await program.methods.initialize().rpc();
-    console.log("Your transaction signature", tx);
+    const collateralType = await program.account.collateralType.fetch(
+      pda(Buffer.from("collateral_type"), collateralMint.toBuffer())
+    );
+    assert.ok(collateralType.keeperFeed.equals(collateralKeeperFeed));
+    assert.ok(collateralType.sortedPositions.equals(sortedPositions.publicKey));
+  });
+
+  it("deposits collateral into a position", async () => {
+    await program.methods
+      .openPosition(positionId)
+      .accounts({ signer: authority, collateralMint })
+      .rpc();
+
+    await program.methods
+      .depositCollateral(positionId, new BN(5_000_000_000))
+      .accountsPartial({
+        signer: authority,
+        collateralMint,
+        sortedPositions: sortedPositions.publicKey,
+        userCollateralAccount: getAssociatedTokenAddressSync(collateralMint, authority),
+      })
+      .rpc();
+
+    const userCollateral = await program.account.userCollateral.fetch(
+      pda(Buffer.from("user_collateral"), authority.toBuffer(), positionId.toArrayLike(Buffer, "le", 8))
+    );
+    assert.ok(userCollateral.collateralAmount.eq(new BN(5_000_000_000)));
+    assert.ok(userCollateral.normalizedDebt.isZero());
+  });
+
+  it("requires the recorded keeper feed", async () => {
+    await expectError(
+      program.methods
+        .mintInrc(positionId, new BN(1_000_000))
+        .accountsPartial({
+          signer: authority,
+          collateralType: pda(Buffer.from("collateral_type"), collateralMint.toBuffer()),
+          sortedPositions: sortedPositions.publicKey,
+          priceFeed: collateralPriceFeed,
+          keeperFeed: null,
+        })
+        .rpc(),
+      "KeeperFeedRequired"
+    );
+  });
+
+  it("rejects a keeper price with a confidence above the price", async () => {
+    await expectError(
+      program.methods
+        .updateKeeperFeed(new BN(100), new BN(101), -2)
+        .accounts({ keeper: authority, keeperFeed: collateralKeeperFeed })
+        .rpc(),
+      "InvalidPrice"
+    );
+  });
+
+  it("only falls back to the keeper when the primary price is stale", async () => {
+    await expectError(
+      program.methods
+        .mintInrc(positionId, new BN(1_000_000))
+        .accountsPartial({
+          signer: authority,
+          collateralType: pda(Buffer.from("collateral_type"), collateralMint.toBuffer()),
+          sortedPositions: sortedPositions.publicKey,
+          priceFeed: collateralPriceFeed,
+          keeperFeed: collateralKeeperFeed,
+        })
+        .rpc(),
+      "InvalidPrice"
+    );
+  });
+
+  it("pauses and unpauses deposits", async () => {
+    const PAUSE_DEPOSIT = 1 << 3;
+    await program.methods.setPausedFlags(PAUSE_DEPOSIT).accounts({ signer: authority }).rpc();
+
+    await expectError(
+      program.methods
+        .depositCollateral(positionId, new BN(1_000))
+        .accountsPartial({
+          signer: authority,
+          collateralMint,
+          sortedPositions: sortedPositions.publicKey,
+          userCollateralAccount: getAssociatedTokenAddressSync(collateralMint, authority),
+        })
+        .rpc(),
+      "DepositPaused"
+    );
+
+    await program.methods.setPausedFlags(0).accounts({ signer: authority }).rpc();
+  });
+
+  it("initializes the stability pool", async () => {
+    await program.methods.initializeStabilityPool().accounts({ authority }).rpc();
+
+    const stabilityPool = await program.account.stabilityPool.fetch(pda(Buffer.from("stability_pool")));
+    assert.ok(stabilityPool.totalDeposits.isZero());
+    assert.ok(stabilityPool.epoch.isZero());
+    assert.ok(stabilityPool.scale.isZero());
   });
 });
+
+function emptyConfigUpdate() {
+  return {
+    usdcPriceFeed: null,
+    usdcPriceFeedId: null,
+    usdcKeeperFeed: null,
+    configUpdateDelay: null,
+    stabilityFeeRate: null,
+    mintFeeBps: null,
+    redeemFeeBps: null,
+    debtCeiling: null,
+    minPositionDebt: null,
+    closeFactorBps: null,
+    fullLiquidationHealthFactor: null,
+    liquidationDust: null,
+    liquidationMode: null,
+    auctionStartDiscountBps: null,
+    auctionDiscountBpsPerSecond: null,
+    auctionMaxDiscountBps: null,
+    auctionDuration: null,
+    insuranceShareBps: null,
+    psmFeeInBps: null,
+    psmFeeOutBps: null,
+    psmCapacity: null,
+    redemptionFeeBps: null,
+    maxPriceConfidenceBps: null,
+    maxOracleDeviationBps: null,
+    liquidationPriceMode: null,
+    auctionPriceMode: null,
+    twapWindow: null,
+  };
+}